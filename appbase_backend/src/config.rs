@@ -9,6 +9,175 @@ pub struct WebConfig {
     pub port: u16,
 }
 
+/// Public-facing site settings used when the backend has to emit absolute
+/// links (feeds, sitemaps). The frontend owns the post routes, so the URL
+/// patterns are configurable; `{id}` and `{tag}` are substituted.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SiteConfig {
+    pub public_url: String,
+    pub title: String,
+    pub description: String,
+    pub post_url_pattern: String,
+    pub tag_url_pattern: String,
+    pub feed_item_limit: u32,
+    pub feed_full_content: bool,
+}
+
+impl Default for SiteConfig {
+    fn default() -> Self {
+        SiteConfig {
+            public_url: "http://127.0.0.1:8080".to_string(),
+            title: "AppBase".to_string(),
+            description: "Latest posts".to_string(),
+            post_url_pattern: "/posts/{id}".to_string(),
+            tag_url_pattern: "/tags/{tag}".to_string(),
+            feed_item_limit: 20,
+            feed_full_content: false,
+        }
+    }
+}
+
+impl SiteConfig {
+    /// Turns a site-relative path into an absolute URL. Absolute URLs are returned unchanged.
+    pub fn absolute_url(&self, path: &str) -> String {
+        if path.starts_with("http://") || path.starts_with("https://") {
+            return path.to_string();
+        }
+        let base = self.public_url.trim_end_matches('/');
+        if path.starts_with('/') {
+            format!("{}{}", base, path)
+        } else {
+            format!("{}/{}", base, path)
+        }
+    }
+
+    /// Public URL of a post on the frontend.
+    pub fn post_url(&self, post_id: &str) -> String {
+        self.absolute_url(&self.post_url_pattern.replace("{id}", post_id))
+    }
+
+    /// Public URL of a tag page on the frontend.
+    pub fn tag_url(&self, tag: &str) -> String {
+        let encoded: String = url::form_urlencoded::byte_serialize(tag.as_bytes()).collect();
+        self.absolute_url(&self.tag_url_pattern.replace("{tag}", &encoded))
+    }
+}
+
+/// One token bucket: clients may send `burst` requests at once, refilled at
+/// `requests_per_minute`. A `requests_per_minute` of 0 disables the limit.
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct RateLimitRule {
+    pub requests_per_minute: u32,
+    pub burst: u32,
+}
+
+/// Per-IP rate limits for each protected scope (see `middleware::rate_limit`).
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    // Key buckets on the first `X-Forwarded-For` entry instead of the peer address.
+    // Clients control that header, so only enable it behind a proxy that overwrites it.
+    pub trust_forwarded_for: bool,
+    pub public_api: RateLimitRule,
+    pub search: RateLimitRule,
+    pub login: RateLimitRule,
+    pub uploads: RateLimitRule,
+    pub comments: RateLimitRule,
+    pub reaction_tokens: RateLimitRule,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        RateLimitConfig {
+            enabled: true,
+            trust_forwarded_for: false,
+            public_api: RateLimitRule { requests_per_minute: 300, burst: 60 },
+            search: RateLimitRule { requests_per_minute: 60, burst: 20 },
+            login: RateLimitRule { requests_per_minute: 10, burst: 5 },
+            uploads: RateLimitRule { requests_per_minute: 30, burst: 10 },
+            comments: RateLimitRule { requests_per_minute: 2, burst: 3 },
+            reaction_tokens: RateLimitRule { requests_per_minute: 6, burst: 5 },
+        }
+    }
+}
+
+/// In-memory LRU cache in front of published-post reads (see `db_operations::read_cache`).
+/// `max_entries = 0` disables it.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ReadCacheConfig {
+    pub enabled: bool,
+    pub max_entries: usize,
+    pub ttl_seconds: u64,
+}
+
+impl Default for ReadCacheConfig {
+    fn default() -> Self {
+        ReadCacheConfig { enabled: true, max_entries: 2048, ttl_seconds: 300 }
+    }
+}
+
+/// Daily view counters (see `helper::view_helpers`). Views are buffered in memory and
+/// written every `flush_interval_seconds`. With `count_on_read = false` only the
+/// `/api/posts/{id}/view` beacon counts, for frontends that prefetch or cache post reads.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ViewCounterConfig {
+    pub enabled: bool,
+    pub count_on_read: bool,
+    pub flush_interval_seconds: u64,
+}
+
+impl Default for ViewCounterConfig {
+    fn default() -> Self {
+        ViewCounterConfig { enabled: true, count_on_read: true, flush_interval_seconds: 10 }
+    }
+}
+
+/// Reader comments (see `helper::comment_helpers`). Submissions with more than
+/// `max_links` links are rejected; everything else waits for moderation.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct CommentsConfig {
+    pub enabled: bool,
+    pub max_links: usize,
+    pub max_content_chars: usize,
+    pub max_reply_depth: u8,
+}
+
+impl Default for CommentsConfig {
+    fn default() -> Self {
+        CommentsConfig { enabled: true, max_links: 2, max_content_chars: 5000, max_reply_depth: 4 }
+    }
+}
+
+/// Outgoing webhooks (see `helper::webhook_helpers`). Endpoints are registered by the
+/// admin; this only tunes delivery. Failed deliveries are retried after
+/// `initial_backoff_seconds`, doubling each time, until `max_attempts` is reached.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct WebhooksConfig {
+    pub dispatch_interval_seconds: u64,
+    pub request_timeout_seconds: u64,
+    pub max_attempts: u32,
+    pub initial_backoff_seconds: u64,
+    pub log_retention_days: u32,
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        WebhooksConfig {
+            dispatch_interval_seconds: 5,
+            request_timeout_seconds: 10,
+            max_attempts: 8,
+            initial_backoff_seconds: 30,
+            log_retention_days: 30,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
     pub web: WebConfig,
@@ -20,6 +189,24 @@ pub struct Config {
     pub session_secret_key: String,
     pub admin_url_prefix: String,
     pub use_secure_cookies: bool, // <-- ADD THIS LINE
+    // NEW: Optional [site] section of config/default.toml
+    #[serde(default)]
+    pub site: SiteConfig,
+    // NEW: Optional [rate_limit] section of config/default.toml
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    // NEW: Optional [read_cache] section of config/default.toml
+    #[serde(default)]
+    pub read_cache: ReadCacheConfig,
+    // NEW: Optional [view_counter] section of config/default.toml
+    #[serde(default)]
+    pub view_counter: ViewCounterConfig,
+    // NEW: Optional [comments] section of config/default.toml
+    #[serde(default)]
+    pub comments: CommentsConfig,
+    // NEW: Optional [webhooks] section of config/default.toml
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
 }
 
 impl Config {
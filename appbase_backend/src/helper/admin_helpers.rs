@@ -1,4 +1,5 @@
-use crate::models::db_operations::{posts_db_operations, users_db_operations};
+use crate::models::db_operations::{posts_db_operations, read_cache, users_db_operations};
+use crate::helper::reaction_helpers;
 use crate::models::Contributor;
 use crate::DbPool;
 use actix_web::web;
@@ -26,6 +27,7 @@ pub struct Settings {
     pub contributor_path_prefix: String,
     pub max_file_upload_size_mb: String,
     pub allowed_mime_types: String,
+    pub reaction_kinds: String,
 }
 
 // Helper to get a connection from the pool
@@ -82,10 +84,14 @@ pub fn get_settings(conn: &Connection) -> Settings {
     let mime_types = users_db_operations::read_setting(conn, "allowed_mime_types")
         .unwrap_or_else(|| "".to_string()); // Secure default
 
+    let reaction_kinds = users_db_operations::read_setting(conn, "reaction_kinds")
+        .unwrap_or_else(|| reaction_helpers::DEFAULT_REACTION_KINDS.to_string());
+
     Settings {
         contributor_path_prefix: prefix,
         max_file_upload_size_mb: max_size,
         allowed_mime_types: mime_types,
+        reaction_kinds,
     }
 }
 
@@ -107,4 +113,9 @@ pub fn delete_tag(db: &web::Data<Database>, tag: &str) -> Result<(), AdminHelper
 
 pub fn get_all_tags(db: &web::Data<Database>) -> Result<Vec<String>, AdminHelperError> {
     Ok(posts_db_operations::get_all_available_tags(db)?)
+}
+
+/// Hit/miss counters of the published-content read cache, for the dashboard.
+pub fn get_read_cache_stats() -> read_cache::ReadCacheStats {
+    read_cache::stats()
 }
\ No newline at end of file
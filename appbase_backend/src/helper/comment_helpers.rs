@@ -0,0 +1,184 @@
+use crate::config::CommentsConfig;
+use crate::helper::sanitization_helpers;
+use crate::models::db_operations::comment_operations;
+use crate::models::db_operations::posts_db_operations::{self, DbError};
+use crate::models::{Comment, CommentThread, PaginatedList, PendingCommentWithPost};
+use actix_web::web;
+use chrono::Utc;
+use redb::Database;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+const MAX_AUTHOR_NAME_CHARS: usize = 80;
+
+/// What a reader submitted. `website` is the honeypot: the comment form hides it, so
+/// only bots fill it in.
+pub struct CommentSubmission<'a> {
+    pub author_name: &'a str,
+    pub content: &'a str,
+    pub parent_id: Option<&'a str>,
+    pub website: Option<&'a str>,
+}
+
+/// How a submission was handled.
+pub enum SubmissionOutcome {
+    /// Stored in the moderation queue.
+    Queued,
+    /// Caught by the honeypot. Answered like `Queued` so bots learn nothing.
+    Discarded,
+    /// Invalid or spam-like; the message is safe to show to the reader.
+    Rejected(String),
+    PostNotFound,
+}
+
+/// Number of links in `text`: `http://`, `https://` and bare `www.` addresses.
+fn count_links(text: &str) -> usize {
+    static LINK_PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = LINK_PATTERN.get_or_init(|| Regex::new(r"(?i)https?://|(?:^|[^/])www\.").unwrap());
+    pattern.find_iter(text).count()
+}
+
+/// Validates a submission, applies the spam heuristics and queues it for moderation.
+pub fn submit_comment(
+    db: &web::Data<Database>,
+    config: &CommentsConfig,
+    post_id: &str,
+    submission: CommentSubmission,
+) -> Result<SubmissionOutcome, DbError> {
+    let Ok(post_uuid) = Uuid::parse_str(post_id) else { return Ok(SubmissionOutcome::PostNotFound) };
+    if posts_db_operations::read_post(db, post_id).is_none() {
+        return Ok(SubmissionOutcome::PostNotFound);
+    }
+    if submission.website.is_some_and(|w| !w.trim().is_empty()) {
+        return Ok(SubmissionOutcome::Discarded);
+    }
+
+    let author_name = submission.author_name.trim();
+    let content = submission.content.trim();
+    if author_name.is_empty() || author_name.chars().count() > MAX_AUTHOR_NAME_CHARS {
+        return Ok(SubmissionOutcome::Rejected(format!("'author_name' must be 1 to {} characters long.", MAX_AUTHOR_NAME_CHARS)));
+    }
+    if content.is_empty() || content.chars().count() > config.max_content_chars {
+        return Ok(SubmissionOutcome::Rejected(format!("'content' must be 1 to {} characters long.", config.max_content_chars)));
+    }
+    if count_links(author_name) > 0 || count_links(content) > config.max_links {
+        return Ok(SubmissionOutcome::Rejected(format!("Comments may contain at most {} links.", config.max_links)));
+    }
+
+    let (parent_id, depth) = match submission.parent_id.map(str::trim).filter(|p| !p.is_empty()) {
+        None => (None, 0),
+        Some(raw_parent) => {
+            let parent = Uuid::parse_str(raw_parent)
+                .ok()
+                .map(|parent| comment_operations::read_approved_comment(db, post_uuid.as_bytes(), parent.as_bytes()))
+                .transpose()?
+                .flatten();
+            match parent {
+                Some(parent) if parent.depth < config.max_reply_depth => (Some(parent.id), parent.depth + 1),
+                Some(_) => return Ok(SubmissionOutcome::Rejected("Replies cannot be nested this deeply.".to_string())),
+                None => return Ok(SubmissionOutcome::Rejected("The comment being replied to does not exist.".to_string())),
+            }
+        }
+    };
+
+    let comment = Comment {
+        id: Uuid::new_v4().to_string(),
+        post_id: post_uuid.to_string(),
+        parent_id,
+        depth,
+        author_name: sanitization_helpers::strip_all_html(author_name),
+        content: sanitization_helpers::sanitize_markdown_content(content),
+        created_at: Utc::now(),
+    };
+    comment_operations::insert_pending_comment(db, &comment)?;
+    Ok(SubmissionOutcome::Queued)
+}
+
+fn into_thread(comment: Comment, children: &mut HashMap<String, Vec<Comment>>) -> CommentThread {
+    let replies = children
+        .remove(&comment.id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|reply| into_thread(reply, children))
+        .collect();
+    CommentThread {
+        id: comment.id,
+        parent_id: comment.parent_id,
+        depth: comment.depth,
+        author_name: comment.author_name,
+        content: comment.content,
+        created_at: comment.created_at,
+        replies,
+    }
+}
+
+/// One page of top-level approved comments of a published post, oldest first, each
+/// with all of its replies. Returns `None` for an unknown post.
+pub fn fetch_comment_threads(
+    db: &web::Data<Database>,
+    post_id: &str,
+    limit: u32,
+    offset: u32,
+) -> Result<Option<PaginatedList<CommentThread>>, DbError> {
+    let Ok(post_uuid) = Uuid::parse_str(post_id) else { return Ok(None) };
+    if posts_db_operations::read_post(db, post_id).is_none() {
+        return Ok(None);
+    }
+
+    let mut roots = Vec::new();
+    let mut children: HashMap<String, Vec<Comment>> = HashMap::new();
+    for comment in comment_operations::read_post_comments(db, post_uuid.as_bytes())? {
+        match comment.parent_id.clone() {
+            Some(parent) => children.entry(parent).or_default().push(comment),
+            None => roots.push(comment),
+        }
+    }
+
+    let total = roots.len() as u64;
+    let threads = roots
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .map(|root| into_thread(root, &mut children))
+        .collect();
+    Ok(Some(PaginatedList::from_offset(threads, total, limit, offset)))
+}
+
+/// One page of the moderation queue, oldest first, with the titles of the posts.
+pub fn fetch_pending_comments(
+    db: &web::Data<Database>,
+    limit: u32,
+    offset: u32,
+) -> Result<PaginatedList<PendingCommentWithPost>, DbError> {
+    let (comments, total) = comment_operations::read_pending_comments(db, limit, offset)?;
+
+    let post_ids: Vec<[u8; 16]> = comments
+        .iter()
+        .filter_map(|c| Uuid::parse_str(&c.post_id).ok().map(Uuid::into_bytes))
+        .collect();
+    let titles: HashMap<String, String> = posts_db_operations::read_post_summaries_by_ids(db, &post_ids)?
+        .into_iter()
+        .map(|post| (post.id, post.metadata.title))
+        .collect();
+
+    let items = comments
+        .into_iter()
+        .map(|comment| {
+            let post_title = titles.get(&comment.post_id).cloned().unwrap_or_else(|| "(unpublished post)".to_string());
+            PendingCommentWithPost { comment, post_title }
+        })
+        .collect();
+    Ok(PaginatedList::from_offset(items, total, limit, offset))
+}
+
+/// Publishes a pending comment.
+pub fn approve_comment(db: &web::Data<Database>, comment_id: &str) -> Result<(), DbError> {
+    comment_operations::approve_comment(db, comment_id)
+}
+
+/// Rejects a pending comment or removes an approved one, replies included.
+pub fn delete_comment(db: &web::Data<Database>, comment_id: &str) -> Result<u64, DbError> {
+    comment_operations::delete_comment(db, comment_id)
+}
@@ -0,0 +1,110 @@
+use crate::models::db_operations::posts_db_operations;
+use crate::models::FullPost;
+use actix_web::http::header::{
+    EntityTag, Header, HttpDate, IfModifiedSince, IfNoneMatch, LastModified, ETag,
+};
+use actix_web::{HttpRequest, HttpResponse, HttpResponseBuilder};
+use chrono::{DateTime, Utc};
+use redb::Database;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Cache validators for one response.
+pub struct Validators {
+    pub etag: EntityTag,
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+/// Identifies one revision of a post: a digest of its id, content and metadata.
+pub fn post_revision_digest(post: &FullPost) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(post.id.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(post.content.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(serde_json::to_vec(&post.metadata).unwrap_or_default());
+    hex::encode(hasher.finalize())[..32].to_string()
+}
+
+impl Validators {
+    /// Strong validators for a single published post, derived from its content and metadata.
+    pub fn for_post(post: &FullPost) -> Self {
+        Validators {
+            etag: EntityTag::new_strong(post_revision_digest(post)),
+            last_modified: Some(post.metadata.last_updated_at.unwrap_or(post.metadata.created_at)),
+        }
+    }
+
+    /// Same validators for another representation of the resource (e.g. rendered HTML),
+    /// so each representation gets its own strong ETag.
+    pub fn for_representation(&self, suffix: &str) -> Self {
+        Validators {
+            etag: EntityTag::new_strong(format!("{}-{}", self.etag.tag(), suffix)),
+            last_modified: self.last_modified,
+        }
+    }
+
+    /// Same validators for a representation that also carries live counters such as
+    /// reactions. The ETag covers the counters; `Last-Modified` is dropped because the
+    /// counters change without the post being edited.
+    pub fn with_counters(&self, counters: &BTreeMap<String, u64>) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(serde_json::to_vec(counters).unwrap_or_default());
+        let digest = hex::encode(hasher.finalize());
+        Validators {
+            etag: EntityTag::new_strong(format!("{}-c{}", self.etag.tag(), &digest[..16])),
+            last_modified: None,
+        }
+    }
+
+    /// Validators for list endpoints. They change whenever a post is approved,
+    /// edited or deleted, or the available tags change (see `bump_publish_state`).
+    pub fn for_published_lists(db: &Database) -> Result<Self, posts_db_operations::DbError> {
+        let (revision, last_modified) = posts_db_operations::read_publish_state(db)?;
+        Ok(Validators {
+            etag: EntityTag::new_strong(format!("posts-r{}", revision)),
+            last_modified,
+        })
+    }
+
+    /// True if the client's cached copy is still current. `If-None-Match` wins
+    /// over `If-Modified-Since` when both are sent (RFC 9110, 13.2.2).
+    pub fn is_not_modified(&self, req: &HttpRequest) -> bool {
+        if req.headers().contains_key(actix_web::http::header::IF_NONE_MATCH) {
+            return match IfNoneMatch::parse(req) {
+                Ok(IfNoneMatch::Any) => true,
+                Ok(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&self.etag)),
+                Err(_) => false,
+            };
+        }
+
+        match (IfModifiedSince::parse(req), self.last_modified) {
+            (Ok(IfModifiedSince(since)), Some(last_modified)) => {
+                // HTTP dates have one-second precision.
+                to_system_time(last_modified) <= SystemTime::from(since)
+            }
+            _ => false,
+        }
+    }
+
+    /// Adds `ETag` and `Last-Modified` to a response builder.
+    pub fn apply(&self, builder: &mut HttpResponseBuilder) {
+        builder.insert_header(ETag(self.etag.clone()));
+        if let Some(last_modified) = self.last_modified {
+            builder.insert_header(LastModified(HttpDate::from(to_system_time(last_modified))));
+        }
+    }
+
+    /// Builds the empty `304 Not Modified` answer.
+    pub fn not_modified_response(&self) -> HttpResponse {
+        let mut builder = HttpResponse::NotModified();
+        self.apply(&mut builder);
+        builder.finish()
+    }
+}
+
+/// Converts to `SystemTime`, dropping sub-second precision like an HTTP date does.
+fn to_system_time(time: DateTime<Utc>) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(time.timestamp().max(0) as u64)
+}
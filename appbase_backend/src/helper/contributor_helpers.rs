@@ -1,5 +1,6 @@
 use crate::models::db_operations::{posts_db_operations, users_db_operations};
-use crate::models::{Contributor, PostSummary, MediaAttachment, FullPost, PendingPostSummaryWithOwner, PostAction};
+use crate::models::db_operations::fuzzy_index_operations::{self, FuzzySources};
+use crate::models::{Contributor, ContributorProfile, PostSummary, MediaAttachment, FullPost, PendingPostSummaryWithOwner, PostAction, PaginatedList, PostViewStats, ProfileLink};
 use crate::config::Config;
 use crate::DbPool;
 use actix_web::{web, web::BytesMut};
@@ -12,7 +13,9 @@ use std::path::{Path, PathBuf};
 use uuid::Uuid;
 use chrono::Utc;
 use std::collections::{HashSet, BTreeMap};
-use crate::helper::sanitization_helpers;
+use crate::helper::{sanitization_helpers, view_helpers};
+use crate::helper::webhook_helpers::{self, WebhookEvent};
+use serde_json::json;
 
 // --- NEW: Secure MIME type to extension mapping ---
 /// Securely maps a validated MIME type to a safe file extension.
@@ -96,6 +99,9 @@ pub fn submit_post_for_approval(
         &clean_keywords, clean_cover_image.as_deref(), has_call_to_action
     )?;
     users_db_operations::add_pending_post_ownership(&conn, &new_post_id, contributor.id)?;
+    webhook_helpers::emit(pool, WebhookEvent::PostSubmitted, json!({
+        "post_id": new_post_id, "title": clean_title, "contributor": contributor.username,
+    }));
     Ok(new_post_id)
 }
 
@@ -156,6 +162,9 @@ pub fn re_submit_for_approval(
         search_keywords_str, cover_image, has_call_to_action
     )?;
 
+    webhook_helpers::emit(pool, WebhookEvent::PostResubmitted, json!({
+        "post_id": post_id, "title": sanitization_helpers::strip_all_html(title), "contributor": editor.username,
+    }));
     Ok(())
 }
 
@@ -166,8 +175,10 @@ pub fn delete_post(
     post_id: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let conn = pool.get()?;
-    posts_db_operations::delete_post(db, &conn, post_id)
-        .map_err(|e| e.into())
+    let title = posts_db_operations::read_post(db, post_id).map(|post| post.metadata.title);
+    posts_db_operations::delete_post(db, &conn, post_id)?;
+    webhook_helpers::emit(pool, WebhookEvent::PostDeleted, json!({ "post_id": post_id, "title": title }));
+    Ok(())
 }
 
 // // NEW: Fetches pending posts for the approval queue.
@@ -197,8 +208,9 @@ pub async fn fetch_pending_posts_with_owners(
     pool: &web::Data<DbPool>,
     limit: u32,
     offset: u32,
-) -> Result<Vec<PendingPostSummaryWithOwner>, Box<dyn std::error::Error>> {
+) -> Result<PaginatedList<PendingPostSummaryWithOwner>, Box<dyn std::error::Error>> {
     let summaries = posts_db_operations::read_all_pending_post_summaries_paginated(db, limit, offset)?;
+    let total = posts_db_operations::count_pending_posts(db)?;
     let mut results = Vec::new();
     let conn = pool.get()?; // Get one connection for all lookups
 
@@ -222,7 +234,7 @@ pub async fn fetch_pending_posts_with_owners(
             );
         }
     }
-    Ok(results)
+    Ok(PaginatedList::from_offset(results, total, limit, offset))
 }
 
 // NEW: Gets full details of a single pending post for review.
@@ -246,6 +258,24 @@ pub fn get_own_post_details(db: &web::Data<Database>, pool: &web::Data<DbPool>,
     posts_db_operations::read_post(db, post_id)
 }
 
+/// View statistics of a published post. Visible to contributors who may edit the post
+/// and to editors who approve posts. `None` when the post is unknown or not allowed.
+pub fn get_post_view_stats(
+    db: &web::Data<Database>,
+    pool: &web::Data<DbPool>,
+    user: &Contributor,
+    post_id: &str,
+    days: u32,
+) -> Result<Option<PostViewStats>, posts_db_operations::DbError> {
+    if !user.can_approve_posts && !can_contributor_perform_action(pool, user, post_id, PostAction::Edit) {
+        return Ok(None);
+    }
+    if posts_db_operations::read_post(db, post_id).is_none() {
+        return Ok(None);
+    }
+    view_helpers::fetch_post_view_stats(db, post_id, days)
+}
+
 
 // NEW: Approves a pending post.
 pub fn approve_post(
@@ -254,7 +284,14 @@ pub fn approve_post(
     post_id: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let conn = pool.get()?;
-    posts_db_operations::approve_post(db, &conn, post_id).map_err(|e| e.into())
+    posts_db_operations::approve_post(db, &conn, post_id)?;
+    let post = posts_db_operations::read_post(db, post_id);
+    webhook_helpers::emit(pool, WebhookEvent::PostApproved, json!({
+        "post_id": post_id,
+        "title": post.as_ref().map(|p| p.metadata.title.clone()),
+        "slug": post.as_ref().and_then(|p| p.metadata.slug.clone()),
+    }));
+    Ok(())
 }
 
 // NEW: Deletes a post from the pending queue.
@@ -277,9 +314,11 @@ pub fn fetch_own_pending_posts(
     user_id: i32,
     limit: u32,
     offset: u32,
-) -> Result<Vec<PostSummary>, posts_db_operations::DbError> {
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
     let conn = pool.get().map_err(|_| posts_db_operations::DbError::NotFound("DB connection failed".to_string()))?;
-    posts_db_operations::read_pending_post_summaries_by_user(db, &conn, user_id, limit, offset)
+    let posts = posts_db_operations::read_pending_post_summaries_by_user(db, &conn, user_id, limit, offset)?;
+    let total = users_db_operations::count_pending_posts_by_user(&conn, user_id)?;
+    Ok(PaginatedList::from_offset(posts, total, limit, offset))
 }
 
 
@@ -433,7 +472,16 @@ pub async fn save_media_attachment(
     
     users_db_operations::add_media_attachment(&conn, &file_id_str, user_id, &tags)?;
 
-    Ok((display_path.replace('\\', "/"), file_id_str))
+    let display_path = display_path.replace('\\', "/");
+    webhook_helpers::emit(&pool, WebhookEvent::MediaUploaded, json!({
+        "media_id": file_id_str,
+        "url": config.site.absolute_url(&display_path),
+        "original_filename": sidecar_data.original_filename,
+        "file_format": sidecar_data.file_format,
+        "file_size": sidecar_data.file_size,
+        "uploaded_by": users_db_operations::get_username_by_id(&conn, user_id).ok(),
+    }));
+    Ok((display_path, file_id_str))
 }
 
 
@@ -444,6 +492,20 @@ fn read_sidecar(path: &Path) -> Result<MediaAttachment, Box<dyn std::error::Erro
 }
 
 
+/// Loads a media attachment's sidecar by id. Only canonical UUIDs are accepted,
+/// so the id is always safe to use as part of a path.
+pub fn read_media_attachment(config: &Config, media_id: &str) -> Option<MediaAttachment> {
+    if Uuid::parse_str(media_id).ok()?.to_string() != media_id {
+        return None;
+    }
+    let sidecar_path = PathBuf::from(&config.media_path)
+        .join("attachments")
+        .join(&media_id[0..2])
+        .join(&media_id[2..4])
+        .join(format!("{}.json", media_id));
+    read_sidecar(&sidecar_path).ok()
+}
+
 pub fn get_user_media(config: &web::Data<Config>, pool: &web::Data<DbPool>, user_id: i32) -> Result<Vec<MediaAttachment>, rusqlite::Error> {
     let conn = pool.get().map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
     let media_ids = users_db_operations::list_media_ids_for_user(&conn, user_id)?;
@@ -517,9 +579,11 @@ pub fn fetch_posts_for_user(
     user_id: i32,
     limit: u32,
     offset: u32,
-) -> Result<Vec<PostSummary>, posts_db_operations::DbError> {
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
     let conn = pool.get().map_err(|_| posts_db_operations::DbError::NotFound("DB connection failed".to_string()))?;
-    posts_db_operations::read_post_summaries_by_user(db, &conn, user_id, limit, offset)
+    let posts = posts_db_operations::read_post_summaries_by_user(db, &conn, user_id, limit, offset)?;
+    let total = users_db_operations::count_posts_by_user(&conn, user_id)?;
+    Ok(PaginatedList::from_offset(posts, total, limit, offset))
 }
 
 
@@ -529,21 +593,23 @@ pub fn search_all_media_by_tag(
     tag_query: &str,
     limit: u32,
     offset: u32,
-) -> Vec<MediaAttachment> {
+) -> PaginatedList<MediaAttachment> {
+    let empty = || PaginatedList::from_offset(Vec::new(), 0, limit, offset);
     let conn = match pool.get() {
         Ok(c) => c,
-        Err(_) => return Vec::new(),
+        Err(_) => return empty(),
     };
     
     let media_ids = match users_db_operations::search_media_by_tag_from_db(&conn, tag_query, limit, offset) {
         Ok(ids) => ids,
-        Err(_) => return Vec::new(),
+        Err(_) => return empty(),
     };
+    let total = users_db_operations::count_media_by_tag(&conn, tag_query).unwrap_or(media_ids.len() as u64);
 
     let mut results = Vec::new();
     let attachments_dir = PathBuf::from(&config.media_path).join("attachments");
 
-    if !attachments_dir.exists() { return results; }
+    if !attachments_dir.exists() { return empty(); }
 
     for media_id in media_ids {
         let dir1 = &media_id[0..2];
@@ -558,7 +624,7 @@ pub fn search_all_media_by_tag(
     }
 
     results.sort_by(|a, b| b.uploaded_at.cmp(&a.uploaded_at));
-    results
+    PaginatedList::from_offset(results, total, limit, offset)
 }
 
 pub fn check_similar_posts(
@@ -592,25 +658,119 @@ pub fn search_posts(
     db: &web::Data<Database>,
     search_type: &str,
     query: &str,
+    fuzzy: bool,
     limit: u32,
     offset: u32,
-) -> Result<Vec<PostSummary>, posts_db_operations::DbError> {
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
+    // Fuzzy matching looks only at the index of the chosen search type.
+    let fuzzy_sources = match search_type {
+        "tag" => Some(FuzzySources { tags: true, keywords: false, titles: false }),
+        "title" => Some(FuzzySources { tags: false, keywords: false, titles: true }),
+        "keyword" => Some(FuzzySources { tags: false, keywords: true, titles: false }),
+        _ => None,
+    };
+    if let (true, Some(sources)) = (fuzzy, fuzzy_sources) {
+        let (ids, total) = fuzzy_index_operations::search(db, query, sources, limit, offset)?;
+        let posts = posts_db_operations::read_post_summaries_by_ids(db, &ids)?;
+        return Ok(PaginatedList::from_offset(posts, total, limit, offset));
+    }
+
     match search_type {
         "post_id" => {
-            posts_db_operations::read_post_summary_by_id(db, query)
-                .map(|opt| opt.into_iter().collect())
+            let posts: Vec<PostSummary> = posts_db_operations::read_post_summary_by_id(db, query)?
+                .into_iter()
+                .collect();
+            let total = posts.len() as u64;
+            Ok(PaginatedList::from_offset(posts, total, limit, offset))
         }
         "tag" => {
-            posts_db_operations::read_post_summaries_by_tag(db, &query.to_lowercase(), limit, offset) // NORMALIZE
+            let lower_tag = query.to_lowercase(); // NORMALIZE
+            let posts = posts_db_operations::read_post_summaries_by_tag(db, &lower_tag, limit, offset)?;
+            let total = posts_db_operations::count_posts_by_tag(db, &lower_tag)?;
+            Ok(PaginatedList::from_offset(posts, total, limit, offset))
         }
         "title" => {
-            posts_db_operations::read_post_summaries_by_title(db, query, limit, offset)
+            let (posts, total) = posts_db_operations::read_post_summaries_by_title(db, query, limit, offset)?;
+            Ok(PaginatedList::from_offset(posts, total, limit, offset))
         }
         "keyword" => { 
-            posts_db_operations::read_post_summaries_by_keyword(db, query, limit, offset)
+            let posts = posts_db_operations::read_post_summaries_by_keyword(db, query, limit, offset)?;
+            let total = posts_db_operations::count_posts_by_keyword(db, query)?;
+            Ok(PaginatedList::from_offset(posts, total, limit, offset))
         }
         _ => {
-            Ok(Vec::new())
+            Ok(PaginatedList::from_offset(Vec::new(), 0, limit, offset))
+        }
+    }
+}
+
+
+// --- Contributor profile ---
+const MAX_DISPLAY_NAME_LENGTH: usize = 80;
+const MAX_BIO_LENGTH: usize = 1000;
+const MAX_PROFILE_LINKS: usize = 8;
+const MAX_LINK_LABEL_LENGTH: usize = 40;
+const MAX_LINK_URL_LENGTH: usize = 500;
+
+pub fn get_own_profile(pool: &web::Data<DbPool>, user_id: i32) -> Result<ContributorProfile, Box<dyn std::error::Error>> {
+    let conn = pool.get()?;
+    Ok(users_db_operations::read_contributor_profile(&conn, user_id)?.unwrap_or_default())
+}
+
+/// Trims and strips HTML; empty values become `None`.
+fn clean_profile_text(value: Option<&str>, max_length: usize, field_name: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let cleaned = sanitization_helpers::strip_all_html(value.unwrap_or("").trim());
+    if cleaned.chars().count() > max_length {
+        return Err(format!("{} cannot exceed {} characters.", field_name, max_length).into());
+    }
+    Ok(Some(cleaned).filter(|v| !v.is_empty()))
+}
+
+/// Validates and saves the contributor's public profile. The avatar must be one of their own uploads.
+pub fn update_own_profile(
+    db: &web::Data<Database>,
+    pool: &web::Data<DbPool>,
+    contributor: &Contributor,
+    profile: &ContributorProfile,
+) -> Result<ContributorProfile, Box<dyn std::error::Error>> {
+    let conn = pool.get()?;
+
+    if profile.links.len() > MAX_PROFILE_LINKS {
+        return Err(format!("A profile can have at most {} links.", MAX_PROFILE_LINKS).into());
+    }
+    let mut links = Vec::new();
+    for link in &profile.links {
+        let url = link.url.trim();
+        if url.is_empty() {
+            continue;
         }
+        if url.len() > MAX_LINK_URL_LENGTH {
+            return Err(format!("Link URLs cannot exceed {} characters.", MAX_LINK_URL_LENGTH).into());
+        }
+        let parsed = url::Url::parse(url).map_err(|_| format!("'{}' is not a valid URL.", url))?;
+        if !matches!(parsed.scheme(), "http" | "https" | "mailto") {
+            return Err("Links must use http, https or mailto.".into());
+        }
+        let label = clean_profile_text(Some(&link.label), MAX_LINK_LABEL_LENGTH, "Link label")?
+            .unwrap_or_else(|| parsed.host_str().unwrap_or(parsed.as_str()).to_string());
+        links.push(ProfileLink { label, url: parsed.to_string() });
     }
-}
\ No newline at end of file
+
+    let avatar_media_id = profile.avatar_media_id.as_deref().map(str::trim).filter(|id| !id.is_empty());
+    if let Some(media_id) = avatar_media_id {
+        if !users_db_operations::is_media_owner(&conn, contributor.id, media_id) {
+            return Err("The avatar must be one of your own uploaded media files.".into());
+        }
+    }
+
+    let cleaned = ContributorProfile {
+        display_name: clean_profile_text(profile.display_name.as_deref(), MAX_DISPLAY_NAME_LENGTH, "Display name")?,
+        bio: clean_profile_text(profile.bio.as_deref(), MAX_BIO_LENGTH, "Bio")?,
+        avatar_media_id: avatar_media_id.map(str::to_string),
+        links,
+    };
+    users_db_operations::upsert_contributor_profile(&conn, contributor.id, &cleaned)?;
+    // Bylines appear in published listings, so their cache validators must change too.
+    posts_db_operations::touch_publish_state(db)?;
+    Ok(cleaned)
+}
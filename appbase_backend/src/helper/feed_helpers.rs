@@ -0,0 +1,187 @@
+use crate::config::SiteConfig;
+use crate::helper::render_helpers::RenderedHtmlCache;
+use crate::models::db_operations::posts_db_operations;
+use crate::models::PostSummary;
+use chrono::{DateTime, Utc};
+use redb::Database;
+use std::sync::Arc;
+
+/// One feed item: the post summary plus, when requested, its rendered body.
+pub struct FeedEntry {
+    pub summary: PostSummary,
+    pub content_html: Option<Arc<String>>,
+}
+
+/// Describes the feed being rendered (site-wide or a single tag).
+pub struct FeedInfo {
+    pub title: String,
+    pub description: String,
+    /// Human-facing page the feed belongs to.
+    pub link: String,
+    /// Absolute URL of the feed document itself.
+    pub self_url: String,
+}
+
+/// Loads the newest published posts, from `CHRONOLOGICAL_INDEX` or, for a tag, from `TAG_INDEX`.
+pub fn fetch_feed_entries(
+    db: &Database,
+    tag: Option<&str>,
+    limit: u32,
+    full_content: bool,
+    render_cache: &RenderedHtmlCache,
+) -> Result<Vec<FeedEntry>, posts_db_operations::DbError> {
+    let summaries = match tag {
+        Some(t) => posts_db_operations::read_post_summaries_by_tag(db, &t.to_lowercase(), limit, 0)?,
+        None => posts_db_operations::read_latest_post_summaries(db, limit, 0)?,
+    };
+
+    Ok(summaries
+        .into_iter()
+        .map(|summary| {
+            let content_html = if full_content {
+                posts_db_operations::read_post(db, &summary.id)
+                    .map(|post| render_cache.get_or_render(&post))
+            } else {
+                None
+            };
+            FeedEntry { summary, content_html }
+        })
+        .collect())
+}
+
+fn entry_updated_at(summary: &PostSummary) -> DateTime<Utc> {
+    summary.metadata.last_updated_at.unwrap_or(summary.metadata.created_at)
+}
+
+/// The feed's own modification time: the newest change among its entries.
+fn feed_updated_at(entries: &[FeedEntry]) -> DateTime<Utc> {
+    entries
+        .iter()
+        .map(|e| entry_updated_at(&e.summary))
+        .max()
+        .unwrap_or_else(Utc::now)
+}
+
+/// Escapes text for use inside an XML element and drops characters XML 1.0 does not allow.
+pub(crate) fn xml_text(input: &str) -> String {
+    let cleaned: String = input
+        .chars()
+        .filter(|c| matches!(c, '\t' | '\n' | '\r') || !c.is_control())
+        .collect();
+    html_escape::encode_text(&cleaned).to_string()
+}
+
+pub(crate) fn xml_attr(input: &str) -> String {
+    let cleaned: String = input.chars().filter(|c| !c.is_control()).collect();
+    html_escape::encode_double_quoted_attribute(&cleaned).to_string()
+}
+
+/// Post titles, summaries, tags and cover URLs are stored entity-escaped (see
+/// `strip_all_html`); decode them before `xml_text`/`xml_attr` so they are escaped once.
+fn stored(input: &str) -> String {
+    html_escape::decode_html_entities(input).into_owned()
+}
+
+/// Best-effort MIME type for a cover image, based on its file extension.
+fn image_mime_type(url: &str) -> &'static str {
+    let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+    if path.ends_with(".png") {
+        "image/png"
+    } else if path.ends_with(".gif") {
+        "image/gif"
+    } else if path.ends_with(".webp") {
+        "image/webp"
+    } else if path.ends_with(".svg") {
+        "image/svg+xml"
+    } else if path.ends_with(".avif") {
+        "image/avif"
+    } else {
+        "image/jpeg"
+    }
+}
+
+/// Renders an RSS 2.0 document.
+pub fn build_rss(site: &SiteConfig, info: &FeedInfo, entries: &[FeedEntry]) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" xmlns:content=\"http://purl.org/rss/1.0/modules/content/\">\n<channel>\n");
+    xml.push_str(&format!("<title>{}</title>\n", xml_text(&info.title)));
+    xml.push_str(&format!("<link>{}</link>\n", xml_text(&info.link)));
+    xml.push_str(&format!("<description>{}</description>\n", xml_text(&info.description)));
+    xml.push_str(&format!(
+        "<atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        xml_attr(&info.self_url)
+    ));
+    xml.push_str(&format!("<lastBuildDate>{}</lastBuildDate>\n", feed_updated_at(entries).to_rfc2822()));
+
+    for entry in entries {
+        let meta = &entry.summary.metadata;
+        let link = site.post_url(&entry.summary.id);
+        xml.push_str("<item>\n");
+        xml.push_str(&format!("<title>{}</title>\n", xml_text(&stored(&meta.title))));
+        xml.push_str(&format!("<link>{}</link>\n", xml_text(&link)));
+        xml.push_str(&format!("<guid isPermaLink=\"false\">urn:uuid:{}</guid>\n", xml_text(&entry.summary.id)));
+        xml.push_str(&format!("<pubDate>{}</pubDate>\n", meta.created_at.to_rfc2822()));
+        xml.push_str(&format!("<description>{}</description>\n", xml_text(&stored(&meta.summary))));
+        if let Some(html) = &entry.content_html {
+            xml.push_str(&format!("<content:encoded>{}</content:encoded>\n", xml_text(html.as_str())));
+        }
+        for tag in &meta.tags {
+            xml.push_str(&format!("<category>{}</category>\n", xml_text(&stored(tag))));
+        }
+        if let Some(cover) = meta.cover_image.as_deref().filter(|c| !c.is_empty()).map(stored) {
+            xml.push_str(&format!(
+                "<enclosure url=\"{}\" length=\"0\" type=\"{}\"/>\n",
+                xml_attr(&site.absolute_url(&cover)),
+                image_mime_type(&cover)
+            ));
+        }
+        xml.push_str("</item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+/// Renders an Atom 1.0 document.
+pub fn build_atom(site: &SiteConfig, info: &FeedInfo, entries: &[FeedEntry]) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!("<id>{}</id>\n", xml_text(&info.self_url)));
+    xml.push_str(&format!("<title>{}</title>\n", xml_text(&info.title)));
+    xml.push_str(&format!("<subtitle>{}</subtitle>\n", xml_text(&info.description)));
+    xml.push_str(&format!("<updated>{}</updated>\n", feed_updated_at(entries).to_rfc3339()));
+    xml.push_str(&format!("<link rel=\"self\" type=\"application/atom+xml\" href=\"{}\"/>\n", xml_attr(&info.self_url)));
+    xml.push_str(&format!("<link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n", xml_attr(&info.link)));
+
+    for entry in entries {
+        let meta = &entry.summary.metadata;
+        let link = site.post_url(&entry.summary.id);
+        xml.push_str("<entry>\n");
+        xml.push_str(&format!("<id>urn:uuid:{}</id>\n", xml_text(&entry.summary.id)));
+        xml.push_str(&format!("<title>{}</title>\n", xml_text(&stored(&meta.title))));
+        xml.push_str(&format!("<link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n", xml_attr(&link)));
+        xml.push_str(&format!("<published>{}</published>\n", meta.created_at.to_rfc3339()));
+        xml.push_str(&format!("<updated>{}</updated>\n", entry_updated_at(&entry.summary).to_rfc3339()));
+        xml.push_str(&format!("<author><name>{}</name></author>\n", xml_text(&site.title)));
+        xml.push_str(&format!("<summary>{}</summary>\n", xml_text(&stored(&meta.summary))));
+        if let Some(html) = &entry.content_html {
+            xml.push_str(&format!("<content type=\"html\">{}</content>\n", xml_text(html.as_str())));
+        }
+        for tag in &meta.tags {
+            xml.push_str(&format!("<category term=\"{}\"/>\n", xml_attr(&stored(tag))));
+        }
+        if let Some(cover) = meta.cover_image.as_deref().filter(|c| !c.is_empty()).map(stored) {
+            xml.push_str(&format!(
+                "<link rel=\"enclosure\" type=\"{}\" href=\"{}\"/>\n",
+                image_mime_type(&cover),
+                xml_attr(&site.absolute_url(&cover))
+            ));
+        }
+        xml.push_str("</entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
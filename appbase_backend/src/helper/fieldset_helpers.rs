@@ -0,0 +1,136 @@
+use actix_web::{web, HttpRequest};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::IntoParams;
+use std::collections::HashSet;
+
+// Field names accepted by `?fields=`. Metadata fields stay nested under `metadata`
+// so a trimmed response has the same shape as the full one.
+const METADATA_FIELDS: &[&str] = &[
+    "title",
+    "created_at",
+    "last_updated_at",
+    "summary",
+    "tags",
+    "cover_image",
+    "has_call_to_action",
+    "search_keywords",
+    "slug",
+    "word_count",
+    "reading_time_minutes",
+    "toc",
+];
+const TOP_LEVEL_FIELDS: &[&str] = &["id", "author", "content", "reactions"];
+const INCLUDES: &[&str] = &["content"];
+
+/// Query parameters shared by every public post endpoint (also used for the OpenAPI spec).
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FieldsQuery {
+    /// Comma-separated fields to return, e.g. `id,title,tags,created_at`. `id` is always included.
+    fields: Option<String>,
+    /// `content` embeds each post's markdown body in list responses, HTML-entity-escaped
+    /// as in the single-post JSON.
+    include: Option<String>,
+}
+
+/// The `?fields=` / `?include=` parameters of a public post request.
+/// `id` is always returned, whatever `fields` says.
+#[derive(Default)]
+pub struct FieldSelection {
+    fields: Option<HashSet<String>>,
+    pub include_content: bool,
+}
+
+fn split_list(raw: &str) -> impl Iterator<Item = String> + '_ {
+    raw.split(',').map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty())
+}
+
+impl FieldSelection {
+    /// Parses the selection from the query string. Unknown names are an error,
+    /// so typos don't silently produce empty objects.
+    pub fn from_request(req: &HttpRequest) -> Result<Self, String> {
+        let query = web::Query::<FieldsQuery>::from_query(req.query_string())
+            .map_err(|_| "Invalid 'fields' or 'include' query parameter.".to_string())?;
+
+        let fields = match query.fields.as_deref() {
+            None => None,
+            Some(raw) => {
+                let mut fields = HashSet::new();
+                for field in split_list(raw) {
+                    if !METADATA_FIELDS.contains(&field.as_str()) && !TOP_LEVEL_FIELDS.contains(&field.as_str()) {
+                        return Err(format!("Unknown field '{}' in 'fields'.", field));
+                    }
+                    fields.insert(field);
+                }
+                Some(fields)
+            }
+        };
+
+        let mut include_content = false;
+        for include in query.include.as_deref().map(split_list).into_iter().flatten() {
+            if !INCLUDES.contains(&include.as_str()) {
+                return Err(format!("Unknown value '{}' in 'include'. Supported: {}.", include, INCLUDES.join(", ")));
+            }
+            include_content = true;
+        }
+
+        Ok(FieldSelection { fields, include_content })
+    }
+
+    /// True when the request asks for the default representation.
+    pub fn is_default(&self) -> bool {
+        self.fields.is_none() && !self.include_content
+    }
+
+    /// Whether `field` (top-level or metadata) should be serialised.
+    pub fn wants(&self, field: &str) -> bool {
+        field == "id" || self.fields.as_ref().is_none_or(|fields| fields.contains(field))
+    }
+
+    fn project(&self, item: &mut Value) {
+        let Some(object) = item.as_object_mut() else { return };
+        if self.fields.is_some() {
+            object.retain(|key, _| key == "metadata" || self.wants(key));
+            if let Some(Value::Object(metadata)) = object.get_mut("metadata") {
+                metadata.retain(|key, _| self.wants(key));
+            }
+        }
+    }
+}
+
+/// Applies the selection to a `FullPost`. `content` is part of a full post, so it
+/// is governed by `fields` alone.
+pub fn shape_post<T: Serialize>(post: &T, selection: &FieldSelection) -> serde_json::Result<Value> {
+    let mut value = serde_json::to_value(post)?;
+    selection.project(&mut value);
+    Ok(value)
+}
+
+/// Applies the selection to a list response: either a bare array of summaries or
+/// a `PaginatedList`. With `include=content`, each body is looked up through
+/// `load_content` (post id -> markdown) and kept even if `fields` omits it. Bodies are
+/// embedded in their stored, HTML-entity-escaped form, like `FullPost::content`.
+pub fn shape_post_list<T: Serialize>(
+    body: &T,
+    selection: &FieldSelection,
+    load_content: impl Fn(&str) -> Option<String>,
+) -> serde_json::Result<Value> {
+    let mut value = serde_json::to_value(body)?;
+    let items = match &mut value {
+        Value::Array(items) => Some(items),
+        Value::Object(object) => object.get_mut("items").and_then(Value::as_array_mut),
+        _ => None,
+    };
+
+    for item in items.into_iter().flatten() {
+        selection.project(item);
+        if selection.include_content {
+            let content = item.get("id").and_then(Value::as_str).and_then(&load_content);
+            if let (Some(object), Some(content)) = (item.as_object_mut(), content) {
+                object.insert("content".to_string(), Value::String(content));
+            }
+        }
+    }
+    Ok(value)
+}
@@ -3,4 +3,13 @@ pub mod contributor_helpers;
 pub mod public_helpers;
 pub mod form_helpers; // NEW
 pub mod advanced_db_manager_helpers;
-pub mod sanitization_helpers;
\ No newline at end of file
+pub mod sanitization_helpers;
+pub mod feed_helpers;
+pub mod sitemap_helpers;
+pub mod conditional_get_helpers;
+pub mod render_helpers;
+pub mod fieldset_helpers;
+pub mod view_helpers;
+pub mod comment_helpers;
+pub mod reaction_helpers;
+pub mod webhook_helpers;
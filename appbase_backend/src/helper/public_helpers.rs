@@ -1,8 +1,18 @@
-use crate::models::db_operations::{posts_db_operations, users_db_operations};
-use crate::models::{FullPost, PostSummary};
+use crate::models::db_operations::{fulltext_index_operations, posts_db_operations, users_db_operations};
+use crate::models::db_operations::fuzzy_index_operations::{self, FuzzySources};
+use crate::models::db_operations::outline_operations;
+use crate::models::db_operations::suggest_operations::{self, SuggestKinds};
+use crate::models::db_operations::tag_filter_operations::{self, TagExpr};
+use crate::models::db_operations::posts_db_operations::PostCursor;
+use crate::config::Config;
+use crate::helper::contributor_helpers;
+use crate::models::{ArchiveMonth, AuthorProfile, FullPost, PaginatedList, PostOutline, PostSummary, Suggestions, TagTreeNode};
 use crate::DbPool;
 use actix_web::web;
+use chrono::{TimeZone, Utc};
 use redb::Database;
+use std::collections::HashMap;
+use uuid::Uuid;
 
 pub fn verify_contributor_credentials(
     pool: &web::Data<DbPool>,
@@ -20,13 +30,55 @@ pub fn fetch_post_by_id(id: &str, db: &web::Data<Database>) -> Option<FullPost>
     posts_db_operations::read_post(db, id)
 }
 
+/// Word count, reading time and heading outline of a published post. Posts published
+/// before these were stored are analysed on the fly.
+pub fn fetch_post_outline(id: &str, db: &web::Data<Database>) -> Option<PostOutline> {
+    let post = fetch_post_by_id(id, db)?;
+    let metadata = post.metadata;
+    Some(match (metadata.word_count, metadata.reading_time_minutes) {
+        (Some(word_count), Some(reading_time_minutes)) => {
+            PostOutline { id: post.id, word_count, reading_time_minutes, toc: metadata.toc }
+        }
+        _ => {
+            let outline = outline_operations::analyze_content(&post.content);
+            PostOutline {
+                id: post.id,
+                word_count: outline.word_count,
+                reading_time_minutes: outline.reading_time_minutes,
+                toc: outline.toc,
+            }
+        }
+    })
+}
+
+/// Resolves a slug to `(post id, current slug)`. See `posts_db_operations::resolve_slug`.
+pub fn resolve_post_slug(
+    slug: &str,
+    db: &web::Data<Database>,
+) -> Result<Option<(String, Option<String>)>, posts_db_operations::DbError> {
+    posts_db_operations::resolve_slug(db, &slug.to_lowercase())
+}
+
 // UPDATED: This function now supports pagination with limit and offset.
 pub fn fetch_latest_posts(
     db: &web::Data<Database>,
     limit: u32,
     offset: u32,
-) -> Result<Vec<PostSummary>, posts_db_operations::DbError> {
-    posts_db_operations::read_latest_post_summaries(db, limit, offset)
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
+    let posts = posts_db_operations::read_latest_post_summaries(db, limit, offset)?;
+    let total = posts_db_operations::count_published_posts(db)?;
+    Ok(PaginatedList::from_offset(posts, total, limit, offset))
+}
+
+/// Keyset-paginated variant of `fetch_latest_posts`.
+pub fn fetch_latest_posts_after(
+    db: &web::Data<Database>,
+    cursor: Option<&PostCursor>,
+    limit: u32,
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
+    let (posts, next_cursor) = posts_db_operations::read_latest_post_summaries_after(db, cursor, limit)?;
+    let total = posts_db_operations::count_published_posts(db)?;
+    Ok(PaginatedList::from_cursor(posts, total, limit, next_cursor.map(|c| c.encode())))
 }
 
 // UPDATED: This function now supports pagination with limit and offset.
@@ -35,8 +87,23 @@ pub fn fetch_posts_by_tag(
     db: &web::Data<Database>,
     limit: u32,
     offset: u32,
-) -> Result<Vec<PostSummary>, posts_db_operations::DbError> {
-    posts_db_operations::read_post_summaries_by_tag(db, &tag.to_lowercase(), limit, offset) // NORMALIZE
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
+    let lower_tag = tag.to_lowercase(); // NORMALIZE
+    let posts = posts_db_operations::read_post_summaries_by_tag(db, &lower_tag, limit, offset)?;
+    let total = posts_db_operations::count_posts_by_tag(db, &lower_tag)?;
+    Ok(PaginatedList::from_offset(posts, total, limit, offset))
+}
+
+/// Keyset-paginated variant of `fetch_posts_by_tag`.
+pub fn fetch_posts_by_tag_after(
+    tag: &str,
+    db: &web::Data<Database>,
+    cursor: Option<&PostCursor>,
+    limit: u32,
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
+    let (posts, next_cursor) = posts_db_operations::read_post_summaries_by_tag_after(db, tag, cursor, limit)?;
+    let total = posts_db_operations::count_posts_by_tag(db, tag)?;
+    Ok(PaginatedList::from_cursor(posts, total, limit, next_cursor.map(|c| c.encode())))
 }
 
 // NEW FUNCTION: This function handles searching for posts by title with pagination.
@@ -45,31 +112,249 @@ pub fn search_posts_by_title(
     db: &web::Data<Database>,
     limit: u32,
     offset: u32,
-) -> Result<Vec<PostSummary>, posts_db_operations::DbError> {
-    posts_db_operations::read_post_summaries_by_title(db, title_query, limit, offset)
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
+    let (posts, total) = posts_db_operations::read_post_summaries_by_title(db, title_query, limit, offset)?;
+    Ok(PaginatedList::from_offset(posts, total, limit, offset))
 }
 
 pub fn fetch_all_available_tags(db: &web::Data<Database>) -> Result<Vec<String>, posts_db_operations::DbError> {
     posts_db_operations::get_all_available_tags(db)
 }
 
+pub fn fetch_tag_tree(db: &web::Data<Database>) -> Result<Vec<TagTreeNode>, posts_db_operations::DbError> {
+    posts_db_operations::read_tag_tree(db)
+}
+
+/// Fetches the posts published in a year, or in one month of it when `month` is given (UTC).
+/// Returns `None` if the year or month is out of range.
+pub fn fetch_archive_posts(
+    db: &web::Data<Database>,
+    year: i32,
+    month: Option<u32>,
+    limit: u32,
+    offset: u32,
+) -> Result<Option<PaginatedList<PostSummary>>, posts_db_operations::DbError> {
+    if !(1..=9999).contains(&year) {
+        return Ok(None);
+    }
+    let (from, until) = match month {
+        Some(m @ 1..=11) => (Utc.with_ymd_and_hms(year, m, 1, 0, 0, 0), Utc.with_ymd_and_hms(year, m + 1, 1, 0, 0, 0)),
+        Some(12) => (Utc.with_ymd_and_hms(year, 12, 1, 0, 0, 0), Utc.with_ymd_and_hms(year + 1, 1, 1, 0, 0, 0)),
+        Some(_) => return Ok(None),
+        None => (Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0), Utc.with_ymd_and_hms(year + 1, 1, 1, 0, 0, 0)),
+    };
+    let (from, until) = match (from.single(), until.single()) {
+        (Some(f), Some(u)) => (f, u),
+        _ => return Ok(None),
+    };
+
+    let (posts, total) = posts_db_operations::read_post_summaries_in_date_range(db, from, until, limit, offset)?;
+    Ok(Some(PaginatedList::from_offset(posts, total, limit, offset)))
+}
+
+pub fn fetch_archive_month_counts(db: &web::Data<Database>) -> Result<Vec<ArchiveMonth>, posts_db_operations::DbError> {
+    posts_db_operations::read_archive_month_counts(db)
+}
+
 pub fn search_posts_by_keyword(
     keyword_query: &str,
     db: &web::Data<Database>,
     limit: u32,
     offset: u32,
-) -> Result<Vec<PostSummary>, posts_db_operations::DbError> {
-    posts_db_operations::read_post_summaries_by_keyword(db, keyword_query, limit, offset)
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
+    let posts = posts_db_operations::read_post_summaries_by_keyword(db, keyword_query, limit, offset)?;
+    let total = posts_db_operations::count_posts_by_keyword(db, keyword_query)?;
+    Ok(PaginatedList::from_offset(posts, total, limit, offset))
+}
+
+/// Keyset-paginated variant of `search_posts_by_keyword`.
+pub fn search_posts_by_keyword_after(
+    keyword_query: &str,
+    db: &web::Data<Database>,
+    cursor: Option<&PostCursor>,
+    limit: u32,
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
+    let (posts, next_cursor) = posts_db_operations::read_post_summaries_by_keyword_after(db, keyword_query, cursor, limit)?;
+    let total = posts_db_operations::count_posts_by_keyword(db, keyword_query)?;
+    Ok(PaginatedList::from_cursor(posts, total, limit, next_cursor.map(|c| c.encode())))
+}
+
+/// "Read next" suggestions for a post. `None` if the post is not published.
+pub fn fetch_related_posts(
+    post_id: &str,
+    db: &web::Data<Database>,
+    limit: u32,
+) -> Result<Option<Vec<PostSummary>>, posts_db_operations::DbError> {
+    posts_db_operations::read_related_post_summaries(db, post_id, limit)
+}
+
+/// Fetches published posts by id, in the given order and without duplicates.
+/// Returns `None` if any id is not a valid UUID.
+pub fn fetch_posts_by_ids(
+    db: &web::Data<Database>,
+    ids: &[&str],
+) -> Result<Option<PaginatedList<PostSummary>>, posts_db_operations::DbError> {
+    let mut id_bytes: Vec<[u8; 16]> = Vec::with_capacity(ids.len());
+    for id in ids {
+        let bytes = match Uuid::parse_str(id) {
+            Ok(uuid) => uuid.into_bytes(),
+            Err(_) => return Ok(None),
+        };
+        if !id_bytes.contains(&bytes) {
+            id_bytes.push(bytes);
+        }
+    }
+    let posts = posts_db_operations::read_post_summaries_by_ids(db, &id_bytes)?;
+    let total = posts.len() as u64;
+    Ok(Some(PaginatedList::from_offset(posts, total, id_bytes.len() as u32, 0)))
+}
+
+/// Full-text search over title, summary and body, ranked by BM25 relevance.
+pub fn search_posts_fulltext(
+    query: &str,
+    db: &web::Data<Database>,
+    limit: u32,
+    offset: u32,
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
+    let (ranked, total) = fulltext_index_operations::search(db, query, limit, offset)?;
+    let ids: Vec<[u8; 16]> = ranked.into_iter().map(|(id, _score)| id).collect();
+    let posts = posts_db_operations::read_post_summaries_by_ids(db, &ids)?;
+    Ok(PaginatedList::from_offset(posts, total, limit, offset))
 }
 
-// --- NEW HELPER FUNCTION ---
-/// Fetches posts that match an intersection of multiple tags, with pagination.
-/// This is a simple passthrough to keep the route handler clean.
-pub fn fetch_posts_by_tags_intersection(
+/// Typo-tolerant search over tags, keywords and title words, closest match first.
+pub fn search_posts_fuzzy(
+    query: &str,
     db: &web::Data<Database>,
-    tags: &[String],
+    sources: FuzzySources,
     limit: u32,
     offset: u32,
-) -> Result<Vec<PostSummary>, posts_db_operations::DbError> {
-    posts_db_operations::read_post_summaries_by_tags_intersection(db, tags, limit, offset)
-}
\ No newline at end of file
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
+    let (ids, total) = fuzzy_index_operations::search(db, query, sources, limit, offset)?;
+    let posts = posts_db_operations::read_post_summaries_by_ids(db, &ids)?;
+    Ok(PaginatedList::from_offset(posts, total, limit, offset))
+}
+
+/// Search-as-you-type completions for a prefix. Tags, keywords and titles are matched lowercase.
+pub fn fetch_suggestions(
+    prefix: &str,
+    db: &web::Data<Database>,
+    kinds: SuggestKinds,
+    limit: u32,
+) -> Result<Suggestions, posts_db_operations::DbError> {
+    suggest_operations::suggest(db, &prefix.to_lowercase(), kinds, limit)
+}
+
+/// Fetches one page of posts matching a boolean tag expression, newest first.
+pub fn fetch_posts_by_tag_expression(
+    db: &web::Data<Database>,
+    expr: &TagExpr,
+    limit: u32,
+    offset: u32,
+) -> Result<PaginatedList<PostSummary>, posts_db_operations::DbError> {
+    let (posts, total) = tag_filter_operations::read_post_summaries_by_tag_expression(db, expr, limit, offset)?;
+    Ok(PaginatedList::from_offset(posts, total, limit, offset))
+}
+
+// --- Authors ---
+
+fn db_connection_error() -> posts_db_operations::DbError {
+    posts_db_operations::DbError::NotFound("DB connection failed".to_string())
+}
+
+/// Resolves avatar media ids to their public URLs, reading each sidecar once.
+struct AvatarResolver<'a> {
+    config: &'a Config,
+    resolved: HashMap<String, Option<String>>,
+}
+
+impl<'a> AvatarResolver<'a> {
+    fn new(config: &'a Config) -> Self {
+        AvatarResolver { config, resolved: HashMap::new() }
+    }
+
+    fn url(&mut self, media_id: Option<&str>) -> Option<String> {
+        let media_id = media_id?;
+        self.resolved
+            .entry(media_id.to_string())
+            .or_insert_with(|| contributor_helpers::read_media_attachment(self.config, media_id).map(|m| m.file_path))
+            .clone()
+    }
+}
+
+/// Fills in the author byline of each summary from `post_ownership`.
+/// A failed lookup is logged and leaves the bylines empty rather than failing the listing.
+pub fn attach_authors(pool: &web::Data<DbPool>, config: &Config, posts: &mut [PostSummary]) {
+    let conn = match pool.get() {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("Could not load post authors: {}", e);
+            return;
+        }
+    };
+    let post_ids: Vec<&str> = posts.iter().map(|p| p.id.as_str()).collect();
+    let mut authors = match users_db_operations::read_post_authors(&conn, &post_ids) {
+        Ok(a) => a,
+        Err(e) => {
+            log::warn!("Could not load post authors: {}", e);
+            return;
+        }
+    };
+
+    let mut avatars = AvatarResolver::new(config);
+    for post in posts.iter_mut() {
+        if let Some((mut info, avatar_media_id)) = authors.remove(&post.id) {
+            info.avatar_url = avatars.url(avatar_media_id.as_deref());
+            post.author = Some(info);
+        }
+    }
+}
+
+pub fn fetch_authors(
+    pool: &web::Data<DbPool>,
+    config: &Config,
+    limit: u32,
+    offset: u32,
+) -> Result<PaginatedList<AuthorProfile>, posts_db_operations::DbError> {
+    let conn = pool.get().map_err(|_| db_connection_error())?;
+    let mut authors = users_db_operations::read_public_authors(&conn, limit, offset)?;
+    let total = users_db_operations::count_public_authors(&conn)?;
+
+    let mut avatars = AvatarResolver::new(config);
+    for author in &mut authors {
+        author.avatar_url = avatars.url(author.avatar_media_id.as_deref());
+    }
+    Ok(PaginatedList::from_offset(authors, total, limit, offset))
+}
+
+pub fn fetch_author(
+    pool: &web::Data<DbPool>,
+    config: &Config,
+    username: &str,
+) -> Result<Option<AuthorProfile>, posts_db_operations::DbError> {
+    let conn = pool.get().map_err(|_| db_connection_error())?;
+    Ok(users_db_operations::read_public_author(&conn, username)?.map(|(_, mut author)| {
+        author.avatar_url = AvatarResolver::new(config).url(author.avatar_media_id.as_deref());
+        author
+    }))
+}
+
+/// Fetches a page of an author's published posts. `None` if there is no such public author.
+pub fn fetch_author_posts(
+    db: &web::Data<Database>,
+    pool: &web::Data<DbPool>,
+    config: &Config,
+    username: &str,
+    limit: u32,
+    offset: u32,
+) -> Result<Option<PaginatedList<PostSummary>>, posts_db_operations::DbError> {
+    let conn = pool.get().map_err(|_| db_connection_error())?;
+    let (user_id, author) = match users_db_operations::read_public_author(&conn, username)? {
+        Some(a) => a,
+        None => return Ok(None),
+    };
+    let mut posts = posts_db_operations::read_post_summaries_by_user(db, &conn, user_id, limit, offset)?;
+    drop(conn);
+    attach_authors(pool, config, &mut posts);
+    Ok(Some(PaginatedList::from_offset(posts, author.post_count, limit, offset)))
+}
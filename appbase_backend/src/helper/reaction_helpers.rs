@@ -0,0 +1,141 @@
+use crate::models::db_operations::posts_db_operations::DbError;
+use crate::models::db_operations::reaction_operations;
+use crate::models::{ReactionResult, ReactionToken};
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use redb::Database;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Value of the `reaction_kinds` setting until an admin changes it.
+pub const DEFAULT_REACTION_KINDS: &str = "like,clap";
+
+const MAX_KINDS: usize = 20;
+const MAX_KIND_CHARS: usize = 32;
+// Tokens are issued for the current period and stay valid through the next one.
+const TOKEN_PERIOD_SECONDS: i64 = 86_400;
+
+/// How a reaction was handled.
+pub enum ReactionOutcome {
+    Reacted(ReactionResult),
+    UnknownKind,
+    InvalidToken,
+    PostNotFound,
+}
+
+/// Parses the comma-separated `reaction_kinds` setting. Kinds are lowercased and may
+/// contain letters, digits, `-` and `_`. An empty list turns reactions off.
+pub fn parse_reaction_kinds(raw: &str) -> Result<Vec<String>, String> {
+    let mut kinds: Vec<String> = Vec::new();
+    for kind in raw.split(',').map(|k| k.trim().to_lowercase()).filter(|k| !k.is_empty()) {
+        let is_valid = kind.len() <= MAX_KIND_CHARS
+            && kind.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !is_valid {
+            return Err(format!(
+                "Invalid reaction kind '{}'. Use at most {} letters, numbers, hyphens and underscores.",
+                kind, MAX_KIND_CHARS
+            ));
+        }
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+    if kinds.len() > MAX_KINDS {
+        return Err(format!("At most {} reaction kinds are allowed.", MAX_KINDS));
+    }
+    Ok(kinds)
+}
+
+/// The key reaction tokens are signed with: `HMAC-SHA256(session key, "reaction-token-key")`,
+/// so the session key itself only ever signs cookies.
+pub fn derive_token_key(session_key: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(session_key).expect("HMAC accepts keys of any length");
+    mac.update(b"reaction-token-key");
+    mac.finalize().into_bytes().into()
+}
+
+fn period_of(timestamp: i64) -> i64 {
+    timestamp.div_euclid(TOKEN_PERIOD_SECONDS)
+}
+
+fn token_mac(secret: &[u8], period: i64, nonce: &[u8; 16]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(format!("reaction-token|{}|{}", period, hex::encode(nonce)).as_bytes());
+    mac
+}
+
+/// Issues a token for the current period. Tokens are `period.nonce.signature`, signed
+/// with the key from `derive_token_key`, and are the only thing that identifies a reader.
+pub fn issue_token(secret: &[u8]) -> ReactionToken {
+    let period = period_of(Utc::now().timestamp());
+    let mut nonce = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let signature = hex::encode(token_mac(secret, period, &nonce).finalize().into_bytes());
+
+    ReactionToken {
+        token: format!("{}.{}.{}", period, hex::encode(nonce), signature),
+        expires_at: DateTime::<Utc>::from_timestamp((period + 2) * TOKEN_PERIOD_SECONDS, 0).unwrap_or_default(),
+    }
+}
+
+/// The `(period, nonce)` of a correctly signed token issued in `current_period` or the one before.
+fn verify_token(secret: &[u8], token: &str, current_period: i64) -> Option<(i64, [u8; 16])> {
+    let mut parts = token.trim().splitn(3, '.');
+    let period: i64 = parts.next()?.parse().ok()?;
+    let nonce: [u8; 16] = hex::decode(parts.next()?).ok()?.try_into().ok()?;
+    let signature = hex::decode(parts.next()?).ok()?;
+
+    if period != current_period && period != current_period - 1 {
+        return None;
+    }
+    token_mac(secret, period, &nonce).verify_slice(&signature).ok()?;
+    Some((period, nonce))
+}
+
+/// Counts per configured kind, including kinds nobody has used yet. Counts of kinds
+/// that were removed from the settings are kept but not shown.
+pub fn fetch_reaction_counts(
+    db: &web::Data<Database>,
+    post_id: &str,
+    kinds: &[String],
+) -> Result<BTreeMap<String, u64>, DbError> {
+    let post_uuid = Uuid::parse_str(post_id)?;
+    let mut reactions: BTreeMap<String, u64> = kinds.iter().map(|kind| (kind.clone(), 0)).collect();
+    for (kind, count) in reaction_operations::read_reaction_counts(db, post_uuid.as_bytes())? {
+        if let Some(entry) = reactions.get_mut(&kind) {
+            *entry = count;
+        }
+    }
+    Ok(reactions)
+}
+
+/// Adds a reader's reaction to a published post, once per token, post and kind.
+pub fn react(
+    db: &web::Data<Database>,
+    secret: &[u8],
+    kinds: &[String],
+    post_id: &str,
+    kind: &str,
+    token: &str,
+) -> Result<ReactionOutcome, DbError> {
+    let Ok(post_uuid) = Uuid::parse_str(post_id) else { return Ok(ReactionOutcome::PostNotFound) };
+    let kind = kind.to_lowercase();
+    if !kinds.contains(&kind) {
+        return Ok(ReactionOutcome::UnknownKind);
+    }
+    let current_period = period_of(Utc::now().timestamp());
+    let Some((period, nonce)) = verify_token(secret, token, current_period) else {
+        return Ok(ReactionOutcome::InvalidToken);
+    };
+
+    let counted = match reaction_operations::add_reaction(db, period, current_period - 1, &nonce, post_uuid.as_bytes(), &kind) {
+        Ok(counted) => counted,
+        Err(DbError::NotFound(_)) => return Ok(ReactionOutcome::PostNotFound),
+        Err(e) => return Err(e),
+    };
+    let reactions = fetch_reaction_counts(db, post_id, kinds)?;
+    Ok(ReactionOutcome::Reacted(ReactionResult { kind, counted, reactions }))
+}
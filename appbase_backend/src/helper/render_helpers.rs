@@ -0,0 +1,43 @@
+use crate::helper::conditional_get_helpers::post_revision_digest;
+use crate::helper::sanitization_helpers;
+use crate::models::FullPost;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Upper bound on cached posts; when full, an arbitrary entry is evicted.
+const MAX_CACHED_POSTS: usize = 2048;
+
+/// Rendered (sanitised) HTML per post, keyed by post id and tagged with the revision
+/// it was rendered from. A new revision simply replaces the old entry, so edits
+/// never serve stale HTML and no explicit invalidation is needed.
+#[derive(Default)]
+pub struct RenderedHtmlCache {
+    entries: Mutex<HashMap<String, (String, Arc<String>)>>,
+}
+
+impl RenderedHtmlCache {
+    /// Returns the HTML for this revision of the post, rendering it on a cache miss.
+    pub fn get_or_render(&self, post: &FullPost) -> Arc<String> {
+        let revision = post_revision_digest(post);
+
+        if let Ok(entries) = self.entries.lock() {
+            if let Some((cached_revision, html)) = entries.get(&post.id) {
+                if *cached_revision == revision {
+                    return html.clone();
+                }
+            }
+        }
+
+        let html = Arc::new(sanitization_helpers::render_markdown_to_html(&post.content));
+
+        if let Ok(mut entries) = self.entries.lock() {
+            if entries.len() >= MAX_CACHED_POSTS && !entries.contains_key(&post.id) {
+                if let Some(evict) = entries.keys().next().cloned() {
+                    entries.remove(&evict);
+                }
+            }
+            entries.insert(post.id.clone(), (revision, html.clone()));
+        }
+        html
+    }
+}
@@ -151,4 +151,33 @@ pub fn strip_all_html(input: &str) -> String {
         .tags(HashSet::new())
         .clean(input)
         .to_string()
-}
\ No newline at end of file
+}
+/// Renders stored post Markdown to HTML that is safe to serve to browsers and feed readers.
+/// Stored content is entity-escaped (see `sanitize_markdown_content`), so it is decoded
+/// first; whatever raw HTML that lets through is then cleaned by ammonia.
+/// Headings get the anchor ids listed in the post's table of contents.
+pub fn render_markdown_to_html(stored_markdown: &str) -> String {
+    use crate::models::db_operations::outline_operations;
+    use pulldown_cmark::{html, Event, Parser, Tag};
+
+    let decoded = html_escape::decode_html_entities(stored_markdown);
+    let outline = outline_operations::analyze_content(stored_markdown);
+    let mut anchors = outline.toc.iter().map(|entry| entry.anchor.as_str());
+
+    let events = Parser::new_ext(&decoded, outline_operations::markdown_options()).map(|event| match event {
+        Event::Start(Tag::Heading(level, _, classes)) => Event::Start(Tag::Heading(level, anchors.next(), classes)),
+        other => other,
+    });
+
+    let mut unsafe_html = String::new();
+    html::push_html(&mut unsafe_html, events);
+
+    let mut builder = ammonia::Builder::default();
+    for tag in ["h1", "h2", "h3", "h4", "h5", "h6"] {
+        builder.add_tag_attributes(tag, &["id"]);
+    }
+    builder
+        .link_rel(Some("nofollow ugc"))
+        .clean(&unsafe_html)
+        .to_string()
+}
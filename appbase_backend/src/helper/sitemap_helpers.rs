@@ -0,0 +1,70 @@
+use crate::config::SiteConfig;
+use crate::helper::feed_helpers::xml_text;
+use crate::models::db_operations::posts_db_operations;
+use chrono::{DateTime, Utc};
+use redb::Database;
+
+/// Maximum number of URLs a single sitemap file may list (sitemaps.org protocol).
+pub const SITEMAP_MAX_URLS: u32 = 50_000;
+
+/// Builds `/sitemap.xml`: a plain `<urlset>` while all posts fit in one file,
+/// otherwise a `<sitemapindex>` pointing at `/sitemaps/posts-{n}.xml`.
+pub fn build_root_sitemap(db: &Database, site: &SiteConfig) -> Result<String, posts_db_operations::DbError> {
+    let total = posts_db_operations::count_published_posts(db)?;
+    if total <= SITEMAP_MAX_URLS as u64 {
+        let entries = posts_db_operations::read_post_lastmods_oldest_first(db, SITEMAP_MAX_URLS, 0)?;
+        return Ok(build_urlset(site, &entries));
+    }
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    let page_lastmods = posts_db_operations::read_page_lastmods_oldest_first(db, SITEMAP_MAX_URLS)?;
+    for (page, newest) in (1..).zip(page_lastmods) {
+        xml.push_str("<sitemap>\n");
+        xml.push_str(&format!(
+            "<loc>{}</loc>\n",
+            xml_text(&site.absolute_url(&format!("/sitemaps/posts-{}.xml", page)))
+        ));
+        if let Some(lastmod) = newest {
+            xml.push_str(&format!("<lastmod>{}</lastmod>\n", lastmod.to_rfc3339()));
+        }
+        xml.push_str("</sitemap>\n");
+    }
+    xml.push_str("</sitemapindex>\n");
+    Ok(xml)
+}
+
+/// Builds one numbered child sitemap (1-based). Returns `None` past the last page.
+pub fn build_sitemap_page(
+    db: &Database,
+    site: &SiteConfig,
+    page: u32,
+) -> Result<Option<String>, posts_db_operations::DbError> {
+    if page == 0 {
+        return Ok(None);
+    }
+    let entries = posts_db_operations::read_post_lastmods_oldest_first(
+        db,
+        SITEMAP_MAX_URLS,
+        (page - 1).saturating_mul(SITEMAP_MAX_URLS),
+    )?;
+    if entries.is_empty() && page > 1 {
+        return Ok(None);
+    }
+    Ok(Some(build_urlset(site, &entries)))
+}
+
+fn build_urlset(site: &SiteConfig, entries: &[(String, DateTime<Utc>)]) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for (post_id, lastmod) in entries {
+        xml.push_str("<url>\n");
+        xml.push_str(&format!("<loc>{}</loc>\n", xml_text(&site.post_url(post_id))));
+        xml.push_str(&format!("<lastmod>{}</lastmod>\n", lastmod.to_rfc3339()));
+        xml.push_str("</url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
@@ -0,0 +1,129 @@
+use crate::models::db_operations::posts_db_operations::{self, DbError};
+use crate::models::db_operations::view_operations::{self, day_number};
+use crate::models::{DailyViews, PopularPost, PostViewStats};
+use actix_web::http::header;
+use actix_web::{web, HttpRequest};
+use chrono::{DateTime, Utc};
+use redb::Database;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use uuid::Uuid;
+
+// Lowercase User-Agent fragments of crawlers, link previews and HTTP tools.
+const BOT_USER_AGENT_MARKERS: &[&str] = &[
+    "bot", "crawl", "spider", "slurp", "facebookexternalhit", "embedly", "preview",
+    "headless", "lighthouse", "curl", "wget", "python-requests", "httpclient", "go-http-client",
+];
+
+/// Whether a request should count as a view: it must come from a browser-like
+/// User-Agent and must not be a prefetch. Nothing about the client is stored.
+pub fn is_countable_request(req: &HttpRequest) -> bool {
+    let is_prefetch = ["Sec-Purpose", "Purpose", "X-Moz"].iter().any(|name| {
+        req.headers()
+            .get(*name)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.to_ascii_lowercase().contains("prefetch"))
+    });
+    if is_prefetch {
+        return false;
+    }
+
+    match req.headers().get(header::USER_AGENT).and_then(|v| v.to_str().ok()) {
+        Some(agent) if !agent.trim().is_empty() => {
+            let agent = agent.to_ascii_lowercase();
+            !BOT_USER_AGENT_MARKERS.iter().any(|marker| agent.contains(marker))
+        }
+        _ => false,
+    }
+}
+
+/// Views counted since the last flush, keyed by `(post id, UTC day)`. Shared by all
+/// workers; `flush` moves them into posts.db in a single write transaction.
+#[derive(Default)]
+pub struct ViewCounter {
+    pending: Mutex<HashMap<([u8; 16], i64), u64>>,
+}
+
+impl ViewCounter {
+    fn lock_pending(&self) -> MutexGuard<'_, HashMap<([u8; 16], i64), u64>> {
+        self.pending.lock().unwrap_or_else(|poisoned| {
+            log::error!("Mutex for buffered view counters was poisoned! Using stale data.");
+            poisoned.into_inner()
+        })
+    }
+
+    /// Counts one view of a post. Invalid ids are ignored.
+    pub fn record(&self, post_id: &str) {
+        let Ok(uuid) = Uuid::parse_str(post_id) else { return };
+        let day = day_number(Utc::now().timestamp());
+        *self.lock_pending().entry((uuid.into_bytes(), day)).or_insert(0) += 1;
+    }
+
+    /// Writes the buffered views. On failure they are put back for the next flush.
+    pub fn flush(&self, db: &Database) {
+        let views = std::mem::take(&mut *self.lock_pending());
+        if let Err(e) = view_operations::record_views(db, &views) {
+            log::error!("Failed to store {} buffered view counters: {}", views.len(), e);
+            let mut pending = self.lock_pending();
+            for (key, count) in views {
+                *pending.entry(key).or_insert(0) += count;
+            }
+        }
+    }
+
+    /// Flushes every `interval` on the current runtime until the server stops.
+    pub fn spawn_flush_task(counter: web::Data<ViewCounter>, db: web::Data<Database>, interval: Duration) {
+        actix_web::rt::spawn(async move {
+            let mut ticker = actix_web::rt::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                counter.flush(&db);
+            }
+        });
+    }
+}
+
+fn format_day(day: i64) -> String {
+    DateTime::<Utc>::from_timestamp(day * 86_400, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Published posts with the most views over the last `days` days (today included).
+pub fn fetch_popular_posts(db: &web::Data<Database>, days: u32, limit: u32) -> Result<Vec<PopularPost>, DbError> {
+    let today = day_number(Utc::now().timestamp());
+    let ranked = view_operations::read_popular(db, today - i64::from(days) + 1, today, limit)?;
+    let ids: Vec<[u8; 16]> = ranked.iter().map(|(id, _)| *id).collect();
+    let views: HashMap<String, u64> =
+        ranked.iter().map(|(id, views)| (Uuid::from_bytes(*id).to_string(), *views)).collect();
+
+    let summaries = posts_db_operations::read_post_summaries_by_ids(db, &ids)?;
+    Ok(summaries
+        .into_iter()
+        .map(|post| PopularPost { views: views.get(&post.id).copied().unwrap_or(0), post })
+        .collect())
+}
+
+/// View statistics of a post, with one `daily` entry per day for the last `days` days.
+/// Returns `None` for an invalid id.
+pub fn fetch_post_view_stats(db: &web::Data<Database>, post_id: &str, days: u32) -> Result<Option<PostViewStats>, DbError> {
+    let Ok(uuid) = Uuid::parse_str(post_id) else { return Ok(None) };
+    let recorded: HashMap<i64, u64> = view_operations::read_post_daily_views(db, uuid.as_bytes())?.into_iter().collect();
+
+    let today = day_number(Utc::now().timestamp());
+    let views_since = |first_day: i64| -> u64 {
+        recorded.iter().filter(|(day, _)| **day >= first_day).map(|(_, views)| views).sum()
+    };
+    let daily = (today - i64::from(days) + 1..=today)
+        .map(|day| DailyViews { date: format_day(day), views: recorded.get(&day).copied().unwrap_or(0) })
+        .collect();
+
+    Ok(Some(PostViewStats {
+        post_id: uuid.to_string(),
+        total_views: recorded.values().sum(),
+        last_7_days: views_since(today - 6),
+        last_30_days: views_since(today - 29),
+        daily,
+    }))
+}
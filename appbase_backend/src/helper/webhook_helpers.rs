@@ -0,0 +1,253 @@
+use crate::config::WebhooksConfig;
+use crate::models::db_operations::webhook_operations::{self, AttemptOutcome, DueDelivery};
+use crate::models::{Webhook, WebhookDelivery};
+use crate::DbPool;
+use actix_web::web;
+use chrono::{DateTime, SecondsFormat, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::time::Duration;
+use thiserror::Error;
+
+// Deliveries sent per dispatcher run; the rest wait for the next run.
+const DELIVERIES_PER_RUN: u32 = 50;
+const MAX_BACKOFF_SECONDS: u64 = 6 * 60 * 60;
+const MAX_ERROR_CHARS: usize = 300;
+
+#[derive(Error, Debug)]
+pub enum WebhookError {
+    #[error("{0}")]
+    Invalid(String),
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("R2D2 Pool error: {0}")]
+    Pool(#[from] r2d2::Error),
+}
+
+/// The events webhooks can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEvent {
+    PostSubmitted,
+    PostApproved,
+    PostResubmitted,
+    PostDeleted,
+    MediaUploaded,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 5] = [
+        WebhookEvent::PostSubmitted,
+        WebhookEvent::PostApproved,
+        WebhookEvent::PostResubmitted,
+        WebhookEvent::PostDeleted,
+        WebhookEvent::MediaUploaded,
+    ];
+
+    /// The name used in payloads, the `X-Webhook-Event` header and event filters.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebhookEvent::PostSubmitted => "post.submitted",
+            WebhookEvent::PostApproved => "post.approved",
+            WebhookEvent::PostResubmitted => "post.resubmitted",
+            WebhookEvent::PostDeleted => "post.deleted",
+            WebhookEvent::MediaUploaded => "media.uploaded",
+        }
+    }
+
+    fn headline(self) -> &'static str {
+        match self {
+            WebhookEvent::PostSubmitted => "New post submitted for approval",
+            WebhookEvent::PostApproved => "Post approved and published",
+            WebhookEvent::PostResubmitted => "Published post edited and re-submitted for approval",
+            WebhookEvent::PostDeleted => "Post deleted",
+            WebhookEvent::MediaUploaded => "Media uploaded",
+        }
+    }
+}
+
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Queues `event` for every webhook subscribed to it. The payload carries `data` plus a
+/// human-readable `text`, so chat tools that expect Slack-style messages can take it as is.
+/// Failures are logged and never affect the action that raised the event.
+pub fn emit(pool: &DbPool, event: WebhookEvent, data: Value) {
+    let now = timestamp(Utc::now());
+    let subject = ["title", "original_filename", "post_id"]
+        .iter()
+        .find_map(|key| data.get(*key).and_then(Value::as_str))
+        .map(str::to_string);
+    let text = match subject {
+        Some(subject) => format!("{}: {}", event.headline(), subject),
+        None => event.headline().to_string(),
+    };
+    let payload = json!({ "event": event.as_str(), "occurred_at": now, "text": text, "data": data }).to_string();
+
+    let queued = pool
+        .get()
+        .map_err(WebhookError::from)
+        .and_then(|conn| Ok(webhook_operations::enqueue_event(&conn, event.as_str(), &payload, &now)?));
+    if let Err(e) = queued {
+        log::error!("Failed to queue webhook event '{}': {}", event.as_str(), e);
+    }
+}
+
+/// `sha256=` followed by the hex HMAC-SHA256 of `"{timestamp}.{body}"` under the webhook's secret.
+fn signature(secret: &str, timestamp: &str, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Sends one delivery. Any 2xx answer counts as delivered; redirects are not followed.
+fn send(agent: &ureq::Agent, delivery: &DueDelivery) -> Result<u16, (Option<u16>, String)> {
+    let sent_at = Utc::now().timestamp().to_string();
+    let result = agent
+        .post(&delivery.url)
+        .set("Content-Type", "application/json")
+        .set("User-Agent", "AppBase-Webhooks")
+        .set("X-Webhook-Event", &delivery.event)
+        .set("X-Webhook-Delivery", &delivery.id.to_string())
+        .set("X-Webhook-Timestamp", &sent_at)
+        .set("X-Webhook-Signature", &signature(&delivery.secret, &sent_at, &delivery.payload))
+        .send_string(&delivery.payload);
+
+    match result {
+        Ok(response) if (200..300).contains(&response.status()) => Ok(response.status()),
+        Ok(response) => Err((Some(response.status()), format!("Unexpected HTTP status {}", response.status()))),
+        Err(ureq::Error::Status(code, _)) => Err((Some(code), format!("HTTP status {}", code))),
+        Err(e) => Err((None, e.to_string())),
+    }
+}
+
+/// Wait before retrying after the given (1-based) failed attempt: doubles every time.
+fn backoff(config: &WebhooksConfig, attempt: u32) -> chrono::Duration {
+    let factor = 1u64 << attempt.saturating_sub(1).min(20);
+    let seconds = config.initial_backoff_seconds.max(1).saturating_mul(factor).min(MAX_BACKOFF_SECONDS);
+    chrono::Duration::seconds(seconds as i64)
+}
+
+/// Sends every due delivery once and records the outcome. Blocking.
+fn dispatch_due(pool: &DbPool, config: &WebhooksConfig, agent: &ureq::Agent) {
+    let conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(e) => {
+            log::error!("Webhook dispatcher could not get a database connection: {}", e);
+            return;
+        }
+    };
+
+    let retention_cutoff = Utc::now() - chrono::Duration::days(i64::from(config.log_retention_days));
+    if let Err(e) = webhook_operations::prune_deliveries(&conn, &timestamp(retention_cutoff)) {
+        log::error!("Failed to prune the webhook delivery log: {}", e);
+    }
+
+    let due = match webhook_operations::read_due_deliveries(&conn, &timestamp(Utc::now()), DELIVERIES_PER_RUN) {
+        Ok(due) => due,
+        Err(e) => {
+            log::error!("Failed to read due webhook deliveries: {}", e);
+            return;
+        }
+    };
+
+    for delivery in due {
+        let attempt = delivery.attempts + 1;
+        let attempted_at = timestamp(Utc::now());
+        let (response_status, error) = match send(agent, &delivery) {
+            Ok(status) => (Some(status), None),
+            Err((status, error)) => {
+                log::warn!("Webhook delivery {} to {} failed (attempt {}): {}", delivery.id, delivery.url, attempt, error);
+                (status, Some(error.chars().take(MAX_ERROR_CHARS).collect::<String>()))
+            }
+        };
+        let retry_at = match error {
+            Some(_) if attempt < config.max_attempts => Some(timestamp(Utc::now() + backoff(config, attempt))),
+            _ => None,
+        };
+
+        let outcome = AttemptOutcome {
+            attempted_at: &attempted_at,
+            response_status,
+            error: error.as_deref(),
+            retry_at: retry_at.as_deref(),
+        };
+        if let Err(e) = webhook_operations::record_attempt(&conn, delivery.id, &outcome) {
+            log::error!("Failed to record webhook delivery {}: {}", delivery.id, e);
+        }
+    }
+}
+
+/// Sends due deliveries every `dispatch_interval_seconds` on the current runtime until
+/// the server stops. Requests run on the blocking thread pool.
+pub fn spawn_dispatcher(pool: web::Data<DbPool>, config: WebhooksConfig) {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(config.request_timeout_seconds.max(1)))
+        .redirects(0)
+        .build();
+    let interval = Duration::from_secs(config.dispatch_interval_seconds.max(1));
+
+    actix_web::rt::spawn(async move {
+        loop {
+            actix_web::rt::time::sleep(interval).await;
+            let (pool, config, agent) = (pool.clone(), config.clone(), agent.clone());
+            if let Err(e) = web::block(move || dispatch_due(&pool, &config, &agent)).await {
+                log::error!("Webhook dispatcher run failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Registers a webhook. Without a secret, a random one is generated. Returns the secret.
+pub fn create_webhook(pool: &web::Data<DbPool>, url: &str, secret: &str, events: &[&str]) -> Result<String, WebhookError> {
+    let is_valid_url = url::Url::parse(url)
+        .map(|parsed| matches!(parsed.scheme(), "http" | "https") && parsed.host_str().is_some())
+        .unwrap_or(false);
+    if !is_valid_url {
+        return Err(WebhookError::Invalid("The webhook URL must be an absolute http:// or https:// URL.".to_string()));
+    }
+    if events.is_empty() {
+        return Err(WebhookError::Invalid("Select at least one event.".to_string()));
+    }
+    if let Some(unknown) = events.iter().find(|e| !WebhookEvent::ALL.iter().any(|known| known.as_str() == **e)) {
+        return Err(WebhookError::Invalid(format!("Unknown webhook event '{}'.", unknown)));
+    }
+
+    let secret = if secret.is_empty() {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        hex::encode(bytes)
+    } else {
+        secret.to_string()
+    };
+
+    let conn = pool.get()?;
+    webhook_operations::create_webhook(&conn, url, &secret, events, &timestamp(Utc::now()))?;
+    Ok(secret)
+}
+
+pub fn fetch_webhooks(pool: &web::Data<DbPool>) -> Result<Vec<Webhook>, WebhookError> {
+    let conn = pool.get()?;
+    Ok(webhook_operations::read_webhooks(&conn)?)
+}
+
+/// The newest entries of the delivery log.
+pub fn fetch_recent_deliveries(pool: &web::Data<DbPool>, limit: u32) -> Result<Vec<WebhookDelivery>, WebhookError> {
+    let conn = pool.get()?;
+    Ok(webhook_operations::read_recent_deliveries(&conn, limit)?)
+}
+
+pub fn delete_webhook(pool: &web::Data<DbPool>, webhook_id: i64) -> Result<usize, WebhookError> {
+    let conn = pool.get()?;
+    Ok(webhook_operations::delete_webhook(&conn, webhook_id)?)
+}
+
+/// Queues an earlier delivery again, with the same payload. Returns the new delivery id.
+pub fn redeliver(pool: &web::Data<DbPool>, delivery_id: i64) -> Result<Option<i64>, WebhookError> {
+    let conn = pool.get()?;
+    Ok(webhook_operations::redeliver(&conn, delivery_id, &timestamp(Utc::now()))?)
+}
@@ -7,6 +7,10 @@ pub type DbPool = Pool<SqliteConnectionManager>;
 
 pub struct AppState {
     pub contributor_prefix: Arc<RwLock<String>>,
+    // Reaction kinds readers may use (the `reaction_kinds` setting), kept here like the prefix.
+    pub reaction_kinds: Arc<RwLock<Vec<String>>>,
+    // Key reaction tokens are signed with, derived from the session key at startup.
+    pub reaction_token_key: [u8; 32],
 }
 
 // --- Existing module declarations ---
@@ -6,8 +6,10 @@ use tera::Tera;
 use appbase_backend::{
     config::Config,
     routes,
-    helper::admin_helpers,
-    middleware::{admin_guard, contributor_guard, ip_guard, ContributorPrefixValidation},
+    helper::{admin_helpers, reaction_helpers, render_helpers::RenderedHtmlCache, view_helpers::ViewCounter, webhook_helpers},
+    setup::db_setup,
+    models::db_operations::read_cache,
+    middleware::{admin_guard, contributor_guard, ip_guard, rate_limit::RateLimiters, ContributorPrefixValidation},
     AppState
 };
 use redb::Database;
@@ -20,6 +22,7 @@ use std::path::PathBuf;
 use rand::prelude::StdRng;
 use hex;
 use std::convert::TryFrom;
+use std::time::Duration;
 
 
 
@@ -49,26 +52,55 @@ async fn main() -> std::io::Result<()> {
 
     let tera = Tera::new("templates/**/*.html").expect("Tera initialization failed");
 
+    // Size the published-content cache before anything reads from posts.db.
+    read_cache::configure(&config.read_cache);
+
     fs::create_dir_all(&config.database_path)
         .expect("Failed to create database directory");
 
     let redb_db_data = web::Data::new(Database::open(&config.posts_db_path())
         .expect("FATAL: posts.db not found. Run 'cargo run --bin setup_cli -- --env-file <path> db setup'"));
 
+    // Create tables introduced by newer versions in an existing posts.db.
+    db_setup::migrate_posts_db(&redb_db_data)
+        .expect("FATAL: Failed to create missing tables in posts.db.");
+
     // --- NEW: Create a thread-safe connection pool for SQLite ---
     let manager = SqliteConnectionManager::file(config.users_db_path());
     let pool = Pool::builder()
         .build(manager)
         .expect("FATAL: Failed to create Rusqlite connection pool.");
 
-    let initial_contributor_prefix = {
+    let (initial_contributor_prefix, initial_reaction_kinds) = {
         let conn = pool.get().expect("Failed to get DB connection for initial setup.");
-        admin_helpers::get_settings(&conn).contributor_path_prefix
+        db_setup::migrate_contributors_db(&conn)
+            .expect("FATAL: Failed to create missing tables in contributors.db.");
+        let settings = admin_helpers::get_settings(&conn);
+        let reaction_kinds = reaction_helpers::parse_reaction_kinds(&settings.reaction_kinds).unwrap_or_else(|e| {
+            log::error!("Ignoring the stored reaction kinds: {}", e);
+            Vec::new()
+        });
+        (settings.contributor_path_prefix, reaction_kinds)
     };
 
-    let app_state = web::Data::new(AppState {
-        contributor_prefix: Arc::new(RwLock::new(initial_contributor_prefix)),
-    });
+    // Rendered post HTML, shared by the content-negotiated post endpoint and the feeds.
+    let render_cache = web::Data::new(RenderedHtmlCache::default());
+
+    // Created once so that every worker shares the same per-IP buckets.
+    let rate_limiters = web::Data::new(RateLimiters::new(&config.rate_limit));
+
+    // Buffered view counters, written to posts.db in the background.
+    let view_counter = web::Data::new(ViewCounter::default());
+    if config.view_counter.enabled {
+        ViewCounter::spawn_flush_task(
+            view_counter.clone(),
+            redb_db_data.clone(),
+            Duration::from_secs(config.view_counter.flush_interval_seconds.max(1)),
+        );
+    }
+
+    // Sends queued webhook deliveries in the background.
+    webhook_helpers::spawn_dispatcher(web::Data::new(pool.clone()), config.webhooks.clone());
 
     // --- MODIFICATION: Load the session key from the config ---
     let session_key_bytes = hex::decode(&config.session_secret_key)
@@ -77,10 +109,19 @@ async fn main() -> std::io::Result<()> {
         .expect("FATAL: The decoded SESSION_SECRET_KEY is not long enough (minimum 64 bytes required).");
     // --- END MODIFICATION ---
 
+    let app_state = web::Data::new(AppState {
+        contributor_prefix: Arc::new(RwLock::new(initial_contributor_prefix)),
+        reaction_kinds: Arc::new(RwLock::new(initial_reaction_kinds)),
+        reaction_token_key: reaction_helpers::derive_token_key(&session_key_bytes),
+    });
+
     let server_address = format!("{}:{}", config.web.host, config.web.port);
     println!("🚀 Server starting at http://{}", server_address);
 
-    HttpServer::new(move || {
+    let shutdown_view_counter = view_counter.clone();
+    let shutdown_db = redb_db_data.clone();
+
+    let server_result = HttpServer::new(move || {
         let session_mw = SessionMiddleware::builder(CookieSessionStore::default(), session_key.clone())
             .cookie_secure(config.use_secure_cookies) // Use configurable value
             .cookie_http_only(true)
@@ -127,8 +168,13 @@ async fn main() -> std::io::Result<()> {
             .app_data(redb_db_data.clone())
             .app_data(web::Data::new(pool.clone())) // Share the connection pool
             .app_data(app_state.clone())
+            .app_data(render_cache.clone())
+            .app_data(rate_limiters.clone())
+            .app_data(view_counter.clone())
 
             .configure(routes::public::config_api)
+            .configure(routes::feeds::config_feeds)
+            .configure(routes::sitemap::config_sitemap)
             .service(actix_files::Files::new("/media", &config.media_path))
             .service(actix_files::Files::new("/ssr_static", "./ssr_static"))
 
@@ -192,5 +238,9 @@ async fn main() -> std::io::Result<()> {
     })
     .bind(server_address)?
     .run()
-    .await
+    .await;
+
+    // Keep the views counted since the last flush.
+    shutdown_view_counter.flush(&shutdown_db);
+    server_result
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use actix_web::{
     // We bring `EitherBody` into scope to help the compiler, though we use it via a helper method.
     body::EitherBody,
-    dev::{self, forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    dev::{self, forward_ready, RequestHead, Service, ServiceRequest, ServiceResponse, Transform},
     guard, web, Error, FromRequest, HttpRequest, HttpResponse,
 };
 use actix_session::{Session, SessionExt};
@@ -11,6 +11,8 @@ use std::env;
 use std::future::{ready, Ready as StdReady};
 use crate::AppState;
 
+pub mod rate_limit;
+
 #[derive(Serialize)]
 pub struct AuthenticatedContributor {
     pub username: String,
@@ -41,6 +43,23 @@ pub fn contributor_guard(session: &Session) -> bool {
     session.get::<String>("role").unwrap_or(None) == Some("contributor".to_string())
 }
 
+/// Resolves the client IP, considering reverse proxies: the first entry of
+/// `X-Forwarded-For` if present, otherwise the peer address.
+pub fn client_ip(head: &RequestHead) -> Option<String> {
+    head.headers()
+        .get("X-Forwarded-For")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.split(',').next()) // Take the first IP if there's a list
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .or_else(|| peer_ip(head))
+}
+
+/// The address of the directly connected peer, ignoring any forwarding headers.
+pub fn peer_ip(head: &RequestHead) -> Option<String> {
+    head.peer_addr.map(|addr| addr.ip().to_string())
+}
+
 pub fn ip_guard(ctx: &guard::GuardContext) -> bool {
     let allowed_ips_str = match env::var("ADMIN_LOGIN_ACCEPT_IP") {
         Ok(val) => val,
@@ -54,17 +73,7 @@ pub fn ip_guard(ctx: &guard::GuardContext) -> bool {
         return true;
     }
 
-    // UPDATED: Get the real IP, considering reverse proxies
-    let request_ip = ctx.head().headers()
-        .get("X-Forwarded-For")
-        .and_then(|v| v.to_str().ok())
-        .and_then(|s| s.split(',').next()) // Take the first IP if there's a list
-        .map(|s| s.trim().to_string())
-        .or_else(|| {
-            ctx.head().peer_addr.map(|addr| addr.ip().to_string())
-        });
-
-    let peer_addr = match request_ip {
+    let peer_addr = match client_ip(ctx.head()) {
         Some(ip) => ip,
         None => {
             log::warn!("Could not determine peer IP address for admin login attempt.");
@@ -0,0 +1,199 @@
+use actix_web::{
+    body::EitherBody,
+    dev::{forward_ready, RequestHead, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::RETRY_AFTER,
+    web, Error, HttpResponse,
+};
+use futures_util::future::{ok, LocalBoxFuture, Ready};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use super::{client_ip, peer_ip};
+use crate::config::{RateLimitConfig, RateLimitRule};
+
+// Once a limiter tracks this many IPs, buckets that have refilled completely are dropped.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// The groups of routes that get their own per-IP budget.
+#[derive(Debug, Clone, Copy)]
+pub enum RateLimitScope {
+    PublicApi,
+    Search,
+    Login,
+    Uploads,
+    Comments,
+    ReactionTokens,
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// A token bucket per client IP for one scope.
+struct Limiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl Limiter {
+    fn new(rule: RateLimitRule) -> Option<Self> {
+        if rule.requests_per_minute == 0 {
+            return None;
+        }
+        Some(Limiter {
+            capacity: rule.burst.max(1) as f64,
+            refill_per_sec: rule.requests_per_minute as f64 / 60.0,
+            buckets: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Takes one token for `ip`. On failure returns the number of seconds until
+    /// the next token is available.
+    fn check(&self, ip: &str) -> Result<(), u64> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|poisoned| {
+            log::error!("Mutex for rate limit buckets was poisoned! Using stale data.");
+            poisoned.into_inner()
+        });
+
+        if buckets.len() >= PRUNE_THRESHOLD {
+            let (capacity, refill_per_sec) = (self.capacity, self.refill_per_sec);
+            buckets.retain(|_, bucket| {
+                let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+                bucket.tokens + elapsed * refill_per_sec < capacity
+            });
+        }
+
+        let bucket = buckets.entry(ip.to_string()).or_insert(Bucket {
+            tokens: self.capacity,
+            last_refill: now,
+        });
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait = (1.0 - bucket.tokens) / self.refill_per_sec;
+            Err(wait.ceil().max(1.0) as u64)
+        }
+    }
+}
+
+/// Shared limiter state, registered once as app data so every worker sees the same buckets.
+pub struct RateLimiters {
+    trust_forwarded_for: bool,
+    public_api: Option<Limiter>,
+    search: Option<Limiter>,
+    login: Option<Limiter>,
+    uploads: Option<Limiter>,
+    comments: Option<Limiter>,
+    reaction_tokens: Option<Limiter>,
+}
+
+impl RateLimiters {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        let build = |rule| if config.enabled { Limiter::new(rule) } else { None };
+        RateLimiters {
+            trust_forwarded_for: config.trust_forwarded_for,
+            public_api: build(config.public_api),
+            search: build(config.search),
+            login: build(config.login),
+            uploads: build(config.uploads),
+            comments: build(config.comments),
+            reaction_tokens: build(config.reaction_tokens),
+        }
+    }
+
+    fn limiter(&self, scope: RateLimitScope) -> Option<&Limiter> {
+        match scope {
+            RateLimitScope::PublicApi => self.public_api.as_ref(),
+            RateLimitScope::Search => self.search.as_ref(),
+            RateLimitScope::Login => self.login.as_ref(),
+            RateLimitScope::Uploads => self.uploads.as_ref(),
+            RateLimitScope::Comments => self.comments.as_ref(),
+            RateLimitScope::ReactionTokens => self.reaction_tokens.as_ref(),
+        }
+    }
+
+    /// The key of a request's bucket: see `RateLimitConfig::trust_forwarded_for`.
+    fn bucket_ip(&self, head: &RequestHead) -> Option<String> {
+        if self.trust_forwarded_for {
+            client_ip(head)
+        } else {
+            peer_ip(head)
+        }
+    }
+}
+
+/// Limits requests per client IP (the peer address, or `X-Forwarded-For` when trusted) for the given scope,
+/// answering `429 Too Many Requests` with `Retry-After` once the bucket is empty.
+pub struct RateLimit(pub RateLimitScope);
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RateLimitMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RateLimitMiddleware { service, scope: self.0 })
+    }
+}
+
+pub struct RateLimitMiddleware<S> {
+    service: S,
+    scope: RateLimitScope,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let limited = req
+            .app_data::<web::Data<RateLimiters>>()
+            .and_then(|limiters| limiters.limiter(self.scope).zip(limiters.bucket_ip(req.head())))
+            .and_then(|(limiter, ip)| limiter.check(&ip).err().map(|retry_after| (ip, retry_after)));
+
+        match limited {
+            None => {
+                let fut = self.service.call(req);
+                Box::pin(async move {
+                    let res = fut.await?;
+                    Ok(res.map_into_left_body())
+                })
+            }
+            Some((ip, retry_after)) => {
+                log::warn!("Rate limit ({:?}) exceeded by {}", self.scope, ip);
+                Box::pin(async move {
+                    let (http_req, _payload) = req.into_parts();
+                    let res = HttpResponse::TooManyRequests()
+                        .insert_header((RETRY_AFTER, retry_after.to_string()))
+                        .json("Too many requests. Please try again later.")
+                        .map_into_right_body();
+                    Ok(ServiceResponse::new(http_req, res))
+                })
+            }
+        }
+    }
+}
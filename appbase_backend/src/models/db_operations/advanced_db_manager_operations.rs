@@ -9,6 +9,7 @@ use uuid::Uuid;
 use crate::models::PostMetadata;
 use crate::models::advanced_db_manager_models::DependentToDelete;
 use super::posts_db_operations as posts_db;
+use super::read_cache;
 
 #[derive(Error, Debug)]
 pub enum AdvancedDbError {
@@ -209,7 +210,9 @@ pub fn delete_redb_rows(db: &Database, main_table: &str, main_row_id: &str, depe
         })?;
     }
 
+    posts_db::bump_publish_state::<AdvancedDbError>(&write_txn)?;
     write_txn.commit()?;
+    read_cache::invalidate_all();
     Ok(())
 }
 
@@ -264,7 +267,9 @@ pub fn clean_redb_table(db: &Database, table_name: &str) -> DbResult<()> {
         }
         Ok(())
     })?;
+    posts_db::bump_publish_state::<AdvancedDbError>(&write_txn)?;
     write_txn.commit()?;
+    read_cache::invalidate_all();
     Ok(())
 }
 
@@ -308,6 +313,8 @@ pub fn update_redb_cell(
         Ok(())
     })?;
 
+    posts_db::bump_publish_state::<AdvancedDbError>(&write_txn)?;
     write_txn.commit()?;
+    read_cache::invalidate_all();
     Ok(())
 }
\ No newline at end of file
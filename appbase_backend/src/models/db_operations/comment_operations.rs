@@ -0,0 +1,179 @@
+use redb::{Database, ReadableTable, TableDefinition, WriteTransaction};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use super::posts_db_operations::DbError;
+use crate::models::Comment;
+
+// --- Tables for reader comments ---
+// (post id, comment id) -> Comment JSON, awaiting moderation.
+pub const PENDING_COMMENTS: TableDefinition<(&[u8; 16], &[u8; 16]), &str> = TableDefinition::new("pending_comments");
+// (post id, comment id) -> Comment JSON, approved and public.
+pub const COMMENTS: TableDefinition<(&[u8; 16], &[u8; 16]), &str> = TableDefinition::new("comments");
+// comment id -> post id, for pending and approved comments alike.
+pub const COMMENT_POSTS: TableDefinition<&[u8; 16], &[u8; 16]> = TableDefinition::new("comment_posts");
+
+const MIN_ID: [u8; 16] = [0u8; 16];
+const MAX_ID: [u8; 16] = [255u8; 16];
+
+/// Comments of one post in `table`, oldest first.
+fn read_comments_of_post(
+    table: &impl ReadableTable<(&'static [u8; 16], &'static [u8; 16]), &'static str>,
+    post_id_bytes: &[u8; 16],
+) -> Result<Vec<Comment>, DbError> {
+    let mut comments = Vec::new();
+    for item_result in table.range((post_id_bytes, &MIN_ID)..=(post_id_bytes, &MAX_ID))? {
+        let (_, value) = item_result?;
+        comments.push(serde_json::from_str::<Comment>(value.value())?);
+    }
+    comments.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+    Ok(comments)
+}
+
+/// Stores a new comment in the moderation queue.
+pub fn insert_pending_comment(db: &Database, comment: &Comment) -> Result<(), DbError> {
+    let post_id_bytes = Uuid::parse_str(&comment.post_id)?.into_bytes();
+    let comment_id_bytes = Uuid::parse_str(&comment.id)?.into_bytes();
+    let comment_json = serde_json::to_string(comment)?;
+
+    let write_txn = db.begin_write()?;
+    {
+        let mut pending = write_txn.open_table(PENDING_COMMENTS)?;
+        let mut locations = write_txn.open_table(COMMENT_POSTS)?;
+        pending.insert((&post_id_bytes, &comment_id_bytes), comment_json.as_str())?;
+        locations.insert(&comment_id_bytes, &post_id_bytes)?;
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+/// An approved comment of the given post, e.g. the parent a reply refers to.
+pub fn read_approved_comment(db: &Database, post_id_bytes: &[u8; 16], comment_id_bytes: &[u8; 16]) -> Result<Option<Comment>, DbError> {
+    let read_txn = db.begin_read()?;
+    let comments = read_txn.open_table(COMMENTS)?;
+    let comment = match comments.get((post_id_bytes, comment_id_bytes))? {
+        Some(guard) => Some(serde_json::from_str(guard.value())?),
+        None => None,
+    };
+    Ok(comment)
+}
+
+/// All approved comments of a post, oldest first.
+pub fn read_post_comments(db: &Database, post_id_bytes: &[u8; 16]) -> Result<Vec<Comment>, DbError> {
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_table(COMMENTS)?;
+    let comments = read_comments_of_post(&table, post_id_bytes)?;
+    Ok(comments)
+}
+
+/// The whole moderation queue, oldest first. Returns one page and the queue length.
+pub fn read_pending_comments(db: &Database, limit: u32, offset: u32) -> Result<(Vec<Comment>, u64), DbError> {
+    let read_txn = db.begin_read()?;
+    let pending = read_txn.open_table(PENDING_COMMENTS)?;
+    let mut comments = Vec::new();
+    for item_result in pending.iter()? {
+        let (_, value) = item_result?;
+        comments.push(serde_json::from_str::<Comment>(value.value())?);
+    }
+    comments.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+    let total = comments.len() as u64;
+    let page = comments.into_iter().skip(offset as usize).take(limit as usize).collect();
+    Ok((page, total))
+}
+
+/// Moves a comment from the moderation queue to the public comments.
+/// A reply whose parent was removed in the meantime becomes a top-level comment.
+pub fn approve_comment(db: &Database, comment_id: &str) -> Result<(), DbError> {
+    let comment_id_bytes = Uuid::parse_str(comment_id)?.into_bytes();
+    let write_txn = db.begin_write()?;
+    {
+        let post_id_bytes = match write_txn.open_table(COMMENT_POSTS)?.get(&comment_id_bytes)? {
+            Some(guard) => *guard.value(),
+            None => return Err(DbError::NotFound(format!("Comment {} not found", comment_id))),
+        };
+        let mut pending = write_txn.open_table(PENDING_COMMENTS)?;
+        let mut comments = write_txn.open_table(COMMENTS)?;
+
+        let mut comment: Comment = match pending.remove((&post_id_bytes, &comment_id_bytes))? {
+            Some(guard) => serde_json::from_str(guard.value())?,
+            None => return Err(DbError::NotFound(format!("Comment {} is not awaiting moderation", comment_id))),
+        };
+        let parent_exists = match comment.parent_id.as_deref().map(Uuid::parse_str) {
+            Some(Ok(parent)) => comments.get((&post_id_bytes, parent.as_bytes()))?.is_some(),
+            _ => false,
+        };
+        if !parent_exists {
+            comment.parent_id = None;
+            comment.depth = 0;
+        }
+        let comment_json = serde_json::to_string(&comment)?;
+        comments.insert((&post_id_bytes, &comment_id_bytes), comment_json.as_str())?;
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+/// Rejects a pending comment or removes an approved one, together with all replies to it.
+/// Returns the number of comments removed.
+pub fn delete_comment(db: &Database, comment_id: &str) -> Result<u64, DbError> {
+    let comment_id_bytes = Uuid::parse_str(comment_id)?.into_bytes();
+    let write_txn = db.begin_write()?;
+    let removed;
+    {
+        let post_id_bytes = match write_txn.open_table(COMMENT_POSTS)?.get(&comment_id_bytes)? {
+            Some(guard) => *guard.value(),
+            None => return Err(DbError::NotFound(format!("Comment {} not found", comment_id))),
+        };
+        let mut pending = write_txn.open_table(PENDING_COMMENTS)?;
+        let mut comments = write_txn.open_table(COMMENTS)?;
+        let mut locations = write_txn.open_table(COMMENT_POSTS)?;
+
+        // Every comment of the post, so that replies at any depth are found.
+        let mut all = read_comments_of_post(&pending, &post_id_bytes)?;
+        all.extend(read_comments_of_post(&comments, &post_id_bytes)?);
+
+        let mut doomed: HashSet<String> = HashSet::from([Uuid::from_bytes(comment_id_bytes).to_string()]);
+        loop {
+            let before = doomed.len();
+            for comment in &all {
+                if comment.parent_id.as_ref().is_some_and(|parent| doomed.contains(parent)) {
+                    doomed.insert(comment.id.clone());
+                }
+            }
+            if doomed.len() == before {
+                break;
+            }
+        }
+
+        for id in &doomed {
+            let id_bytes = Uuid::parse_str(id)?.into_bytes();
+            pending.remove((&post_id_bytes, &id_bytes))?;
+            comments.remove((&post_id_bytes, &id_bytes))?;
+            locations.remove(&id_bytes)?;
+        }
+        removed = doomed.len() as u64;
+    }
+    write_txn.commit()?;
+    Ok(removed)
+}
+
+/// Removes every comment of a post inside the caller's write transaction.
+pub fn remove_post_comments(txn: &WriteTransaction, post_id_bytes: &[u8; 16]) -> Result<(), DbError> {
+    let mut locations = txn.open_table(COMMENT_POSTS)?;
+    for definition in [PENDING_COMMENTS, COMMENTS] {
+        let mut table = txn.open_table(definition)?;
+        let ids: Vec<[u8; 16]> = {
+            let mut ids = Vec::new();
+            for item_result in table.range((post_id_bytes, &MIN_ID)..=(post_id_bytes, &MAX_ID))? {
+                let (key, _) = item_result?;
+                ids.push(*key.value().1);
+            }
+            ids
+        };
+        for id in ids {
+            table.remove((post_id_bytes, &id))?;
+            locations.remove(&id)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,257 @@
+use redb::{Database, ReadableTable, TableDefinition, WriteTransaction};
+use pulldown_cmark::{Event, Parser};
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::posts_db_operations::DbError;
+
+// --- Tables for the full-text (inverted) index of PUBLISHED posts ---
+// (stemmed term, post id) -> weighted term frequency
+pub const FULLTEXT_POSTINGS: TableDefinition<(&str, &[u8; 16]), u32> = TableDefinition::new("fulltext_postings");
+// post id -> JSON `FullTextDoc`, so a post's postings can be removed without re-reading its content
+pub const FULLTEXT_DOCS: TableDefinition<&[u8; 16], &str> = TableDefinition::new("fulltext_docs");
+// Corpus statistics needed for BM25 ("total_length").
+pub const FULLTEXT_STATS: TableDefinition<&str, u64> = TableDefinition::new("fulltext_stats");
+
+const TOTAL_LENGTH_KEY: &str = "total_length";
+
+// Field weights: a hit in the title counts more than one in the body.
+const TITLE_WEIGHT: u32 = 3;
+const SUMMARY_WEIGHT: u32 = 2;
+const CONTENT_WEIGHT: u32 = 1;
+
+// Standard BM25 tuning constants.
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "above", "after", "again", "against", "all", "am", "an", "and", "any", "are", "as", "at",
+    "be", "because", "been", "before", "being", "below", "between", "both", "but", "by", "can", "could",
+    "did", "do", "does", "doing", "down", "during", "each", "few", "for", "from", "further", "had", "has",
+    "have", "having", "he", "her", "here", "hers", "herself", "him", "himself", "his", "how", "i", "if",
+    "in", "into", "is", "it", "its", "itself", "just", "me", "more", "most", "my", "myself", "no", "nor",
+    "not", "now", "of", "off", "on", "once", "only", "or", "other", "our", "ours", "ourselves", "out",
+    "over", "own", "same", "she", "should", "so", "some", "such", "than", "that", "the", "their",
+    "theirs", "them", "themselves", "then", "there", "these", "they", "this", "those", "through", "to",
+    "too", "under", "until", "up", "very", "was", "we", "were", "what", "when", "where", "which", "while",
+    "who", "whom", "why", "will", "with", "would", "you", "your", "yours", "yourself", "yourselves",
+];
+
+/// A post id with its relevance score.
+pub type ScoredPost = ([u8; 16], f64);
+
+/// What we remember about an indexed post.
+#[derive(Serialize, Deserialize)]
+struct FullTextDoc {
+    length: u32,
+    terms: Vec<String>,
+}
+
+/// Splits text into lowercase words, drops stop-words and stems what is left.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let stemmer = Stemmer::create(Algorithm::English);
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .filter(|word| word.chars().count() > 1 && word.len() <= 40)
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .map(|word| stemmer.stem(&word).into_owned())
+        .collect()
+}
+
+/// Extracts the readable text of a stored Markdown body. Content is stored
+/// HTML-escaped, so entities are decoded first; link targets and markup are dropped.
+fn markdown_to_plain_text(markdown: &str) -> String {
+    let decoded = html_escape::decode_html_entities(markdown);
+    let mut text = String::new();
+    for event in Parser::new(&decoded) {
+        match event {
+            Event::Text(t) | Event::Code(t) => {
+                text.push_str(&t);
+                text.push(' ');
+            }
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Removes every posting of a post. Does nothing if the post was never indexed.
+pub fn remove_post(txn: &WriteTransaction, post_id_bytes: &[u8; 16]) -> Result<(), DbError> {
+    let mut docs_table = txn.open_table(FULLTEXT_DOCS)?;
+    let old_doc: Option<FullTextDoc> = docs_table
+        .remove(post_id_bytes)?
+        .map(|guard| serde_json::from_str(guard.value()))
+        .transpose()?;
+
+    if let Some(doc) = old_doc {
+        let mut postings = txn.open_table(FULLTEXT_POSTINGS)?;
+        for term in &doc.terms {
+            postings.remove((term.as_str(), post_id_bytes))?;
+        }
+
+        let mut stats = txn.open_table(FULLTEXT_STATS)?;
+        let total_length = stats.get(TOTAL_LENGTH_KEY)?.map(|g| g.value()).unwrap_or(0);
+        stats.insert(TOTAL_LENGTH_KEY, total_length.saturating_sub(doc.length as u64))?;
+    }
+    Ok(())
+}
+
+/// (Re-)indexes a published post inside the caller's write transaction.
+/// Title and summary are stored entity-escaped, like the body, and are decoded first.
+pub fn index_post(
+    txn: &WriteTransaction,
+    post_id_bytes: &[u8; 16],
+    title: &str,
+    summary: &str,
+    content: &str,
+) -> Result<(), DbError> {
+    remove_post(txn, post_id_bytes)?;
+
+    let mut frequencies: HashMap<String, u32> = HashMap::new();
+    let fields = [
+        (html_escape::decode_html_entities(title).into_owned(), TITLE_WEIGHT),
+        (html_escape::decode_html_entities(summary).into_owned(), SUMMARY_WEIGHT),
+        (markdown_to_plain_text(content), CONTENT_WEIGHT),
+    ];
+    let mut length = 0u32;
+    for (text, weight) in &fields {
+        for term in tokenize(text) {
+            *frequencies.entry(term).or_insert(0) += weight;
+            length += weight;
+        }
+    }
+
+    {
+        let mut postings = txn.open_table(FULLTEXT_POSTINGS)?;
+        for (term, frequency) in &frequencies {
+            postings.insert((term.as_str(), post_id_bytes), *frequency)?;
+        }
+    }
+
+    let doc = FullTextDoc { length, terms: frequencies.into_keys().collect() };
+    let doc_json = serde_json::to_string(&doc)?;
+    txn.open_table(FULLTEXT_DOCS)?.insert(post_id_bytes, doc_json.as_str())?;
+
+    let mut stats = txn.open_table(FULLTEXT_STATS)?;
+    let total_length = stats.get(TOTAL_LENGTH_KEY)?.map(|g| g.value()).unwrap_or(0);
+    stats.insert(TOTAL_LENGTH_KEY, total_length + length as u64)?;
+    Ok(())
+}
+
+/// Ranks published posts against `query` with BM25.
+/// Returns one page of `(post id, score)` pairs, best first, and the number of matching posts.
+pub fn search(
+    db: &Database,
+    query: &str,
+    limit: u32,
+    offset: u32,
+) -> Result<(Vec<ScoredPost>, u64), DbError> {
+    let mut query_terms = tokenize(query);
+    query_terms.sort_unstable();
+    query_terms.dedup();
+    if query_terms.is_empty() {
+        return Ok((Vec::new(), 0));
+    }
+
+    let read_txn = db.begin_read()?;
+    let postings = read_txn.open_table(FULLTEXT_POSTINGS)?;
+    let docs_table = read_txn.open_table(FULLTEXT_DOCS)?;
+    let stats = read_txn.open_table(FULLTEXT_STATS)?;
+
+    let doc_count = docs_table.len()?;
+    if doc_count == 0 {
+        return Ok((Vec::new(), 0));
+    }
+    let total_length = stats.get(TOTAL_LENGTH_KEY)?.map(|g| g.value()).unwrap_or(0);
+    let avg_length = (total_length as f64 / doc_count as f64).max(1.0);
+
+    let mut doc_lengths: HashMap<[u8; 16], f64> = HashMap::new();
+    let mut scores: HashMap<[u8; 16], f64> = HashMap::new();
+
+    for term in &query_terms {
+        let start_key = (term.as_str(), &[0u8; 16]);
+        let end_key = (term.as_str(), &[255u8; 16]);
+
+        let mut matches: Vec<([u8; 16], u32)> = Vec::new();
+        for item_result in postings.range(start_key..=end_key)? {
+            let (key, frequency) = item_result?;
+            matches.push((*key.value().1, frequency.value()));
+        }
+
+        let doc_frequency = matches.len() as f64;
+        let idf = ((doc_count as f64 - doc_frequency + 0.5) / (doc_frequency + 0.5) + 1.0).ln();
+
+        for (post_id, frequency) in matches {
+            let doc_length = match doc_lengths.get(&post_id) {
+                Some(length) => *length,
+                None => {
+                    let length = docs_table
+                        .get(&post_id)?
+                        .and_then(|g| serde_json::from_str::<FullTextDoc>(g.value()).ok())
+                        .map(|doc| doc.length as f64)
+                        .unwrap_or(avg_length);
+                    doc_lengths.insert(post_id, length);
+                    length
+                }
+            };
+            let tf = frequency as f64;
+            let term_score = idf * (tf * (BM25_K1 + 1.0))
+                / (tf + BM25_K1 * (1.0 - BM25_B + BM25_B * doc_length / avg_length));
+            *scores.entry(post_id).or_insert(0.0) += term_score;
+        }
+    }
+
+    let mut ranked: Vec<ScoredPost> = scores.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let total = ranked.len() as u64;
+
+    let page = ranked
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .collect();
+    Ok((page, total))
+}
+
+/// Drops and rebuilds the whole index from the published `POSTS` / `METADATA` tables.
+/// Used by the setup CLI for databases that predate the index.
+pub fn rebuild_index(db: &Database) -> Result<u64, DbError> {
+    use super::posts_db_operations::{METADATA, POSTS};
+    use crate::models::PostMetadata;
+
+    let write_txn = db.begin_write()?;
+    let mut indexed = 0u64;
+    {
+        write_txn.delete_table(FULLTEXT_POSTINGS)?;
+        write_txn.delete_table(FULLTEXT_DOCS)?;
+        write_txn.delete_table(FULLTEXT_STATS)?;
+
+        let posts: Vec<([u8; 16], String, PostMetadata)> = {
+            let posts_table = write_txn.open_table(POSTS)?;
+            let metadata_table = write_txn.open_table(METADATA)?;
+            let mut posts = Vec::new();
+            for item_result in metadata_table.iter()? {
+                let (id_guard, meta_guard) = item_result?;
+                let post_id = *id_guard.value();
+                let metadata: PostMetadata = match serde_json::from_str(meta_guard.value()) {
+                    Ok(m) => m,
+                    Err(_) => continue,
+                };
+                if let Some(content_guard) = posts_table.get(&post_id)? {
+                    posts.push((post_id, content_guard.value().to_string(), metadata));
+                }
+            }
+            posts
+        };
+
+        for (post_id, content, metadata) in &posts {
+            index_post(&write_txn, post_id, &metadata.title, &metadata.summary, content)?;
+            indexed += 1;
+        }
+    }
+    write_txn.commit()?;
+    Ok(indexed)
+}
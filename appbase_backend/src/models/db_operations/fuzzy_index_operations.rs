@@ -0,0 +1,230 @@
+use redb::{Database, ReadableTable, TableDefinition, WriteTransaction};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::posts_db_operations::{DbError, SEARCH_APPEAR_KEYWORD_INDEX, TAG_INDEX};
+use super::suggest_operations::TITLE_WORD_INDEX;
+
+// --- Tables for typo-tolerant search over the terms of PUBLISHED posts ---
+// Every distinct tag, search keyword and title word -> number of index entries using it.
+pub const FUZZY_TERMS: TableDefinition<&str, u64> = TableDefinition::new("fuzzy_terms");
+// (trigram, term) -> (). Candidates for a misspelling are the terms sharing a trigram with it.
+pub const FUZZY_TRIGRAMS: TableDefinition<(&str, &str), ()> = TableDefinition::new("fuzzy_trigrams");
+
+// Upper bound on postings read per trigram, so very common trigrams stay cheap.
+const MAX_TRIGRAM_POSTINGS: usize = 5_000;
+// Every query word costs its own trigram scans, so queries are kept short.
+pub const MAX_QUERY_CHARS: usize = 100;
+pub const MAX_QUERY_WORDS: usize = 8;
+
+/// The indices a fuzzy search looks at.
+#[derive(Debug, Clone, Copy)]
+pub struct FuzzySources {
+    pub tags: bool,
+    pub keywords: bool,
+    pub titles: bool,
+}
+
+impl Default for FuzzySources {
+    fn default() -> Self {
+        FuzzySources { tags: true, keywords: true, titles: true }
+    }
+}
+
+/// Character trigrams of a term, padded so that the first and last letters count as well.
+fn trigrams(term: &str) -> Vec<String> {
+    let padded: Vec<char> = format!("^^{}$", term).chars().collect();
+    let mut grams: Vec<String> = padded.windows(3).map(|w| w.iter().collect()).collect();
+    grams.sort_unstable();
+    grams.dedup();
+    grams
+}
+
+/// How many edits a query term of this length may be away from an indexed term.
+fn max_distance(term: &str) -> usize {
+    match term.chars().count() {
+        0..=2 => 0,
+        3..=5 => 1,
+        6..=9 => 2,
+        _ => 3,
+    }
+}
+
+/// Records newly created index entries for `terms` (one per entry, duplicates allowed).
+pub fn add_terms(txn: &WriteTransaction, terms: &[String]) -> Result<(), DbError> {
+    if terms.is_empty() {
+        return Ok(());
+    }
+    let mut terms_table = txn.open_table(FUZZY_TERMS)?;
+    let mut trigram_table = txn.open_table(FUZZY_TRIGRAMS)?;
+    for term in terms {
+        let count = terms_table.get(term.as_str())?.map(|g| g.value()).unwrap_or(0);
+        if count == 0 {
+            for gram in trigrams(term) {
+                trigram_table.insert((gram.as_str(), term.as_str()), ())?;
+            }
+        }
+        terms_table.insert(term.as_str(), count + 1)?;
+    }
+    Ok(())
+}
+
+/// Records removed index entries for `terms`. A term is dropped once nothing uses it.
+pub fn remove_terms(txn: &WriteTransaction, terms: &[String]) -> Result<(), DbError> {
+    if terms.is_empty() {
+        return Ok(());
+    }
+    let mut terms_table = txn.open_table(FUZZY_TERMS)?;
+    let mut trigram_table = txn.open_table(FUZZY_TRIGRAMS)?;
+    for term in terms {
+        let count = terms_table.get(term.as_str())?.map(|g| g.value()).unwrap_or(0);
+        if count > 1 {
+            terms_table.insert(term.as_str(), count - 1)?;
+        } else {
+            terms_table.remove(term.as_str())?;
+            for gram in trigrams(term) {
+                trigram_table.remove((gram.as_str(), term.as_str()))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Indexed terms within the allowed edit distance of `query_term`, with their distance.
+fn matching_terms(
+    trigram_table: &redb::ReadOnlyTable<(&str, &str), ()>,
+    query_term: &str,
+) -> Result<Vec<(String, usize)>, DbError> {
+    let max = max_distance(query_term);
+    let query_len = query_term.chars().count();
+
+    let mut candidates: HashSet<String> = HashSet::new();
+    for gram in trigrams(query_term) {
+        for item_result in trigram_table.range((gram.as_str(), "")..)?.take(MAX_TRIGRAM_POSTINGS) {
+            let (key, _) = item_result?;
+            let (key_gram, term) = key.value();
+            if key_gram != gram {
+                break;
+            }
+            if term.chars().count().abs_diff(query_len) <= max {
+                candidates.insert(term.to_string());
+            }
+        }
+    }
+
+    Ok(candidates
+        .into_iter()
+        .filter_map(|term| {
+            let distance = strsim::osa_distance(query_term, &term);
+            (distance <= max).then_some((term, distance))
+        })
+        .collect())
+}
+
+/// Finds published posts whose tags, keywords or title words are within a small edit
+/// distance of the query (or of one of its words, for multi-word queries).
+/// Returns one page of post ids, closest match first and newer posts first among equals,
+/// and the number of matching posts. Repeated words are looked up once, and words past
+/// `MAX_QUERY_WORDS` are ignored; callers reject longer queries.
+pub fn search(
+    db: &Database,
+    query: &str,
+    sources: FuzzySources,
+    limit: u32,
+    offset: u32,
+) -> Result<(Vec<[u8; 16]>, u64), DbError> {
+    let query = query.trim().to_lowercase();
+    let mut query_terms: Vec<&str> = vec![query.as_str()];
+    let words: Vec<&str> = query.split_whitespace().take(MAX_QUERY_WORDS).collect();
+    if words.len() > 1 {
+        for word in words {
+            if !query_terms.contains(&word) {
+                query_terms.push(word);
+            }
+        }
+    }
+
+    let read_txn = db.begin_read()?;
+    let trigram_table = read_txn.open_table(FUZZY_TRIGRAMS)?;
+
+    // term -> closest distance to any query term
+    let mut terms: BTreeMap<String, usize> = BTreeMap::new();
+    for query_term in query_terms.iter().filter(|t| !t.is_empty()) {
+        for (term, distance) in matching_terms(&trigram_table, query_term)? {
+            let best = terms.entry(term).or_insert(distance);
+            *best = (*best).min(distance);
+        }
+    }
+    if terms.is_empty() {
+        return Ok((Vec::new(), 0));
+    }
+
+    let mut indices = Vec::new();
+    if sources.tags {
+        indices.push(read_txn.open_table(TAG_INDEX)?);
+    }
+    if sources.keywords {
+        indices.push(read_txn.open_table(SEARCH_APPEAR_KEYWORD_INDEX)?);
+    }
+    if sources.titles {
+        indices.push(read_txn.open_table(TITLE_WORD_INDEX)?);
+    }
+
+    // post id -> (best distance, negated timestamp)
+    let mut best: HashMap<[u8; 16], (usize, i64)> = HashMap::new();
+    for (term, distance) in &terms {
+        let start_key = (term.as_str(), i64::MIN, &[0u8; 16]);
+        let end_key = (term.as_str(), i64::MAX, &[255u8; 16]);
+        for index in &indices {
+            for item_result in index.range(start_key..=end_key)? {
+                let (key, _) = item_result?;
+                let (_, timestamp, post_id) = key.value();
+                let entry = best.entry(*post_id).or_insert((*distance, timestamp));
+                entry.0 = entry.0.min(*distance);
+            }
+        }
+    }
+
+    let mut ranked: Vec<([u8; 16], (usize, i64))> = best.into_iter().collect();
+    ranked.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    let total = ranked.len() as u64;
+
+    let page = ranked
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .map(|(id, _)| id)
+        .collect();
+    Ok((page, total))
+}
+
+/// Drops and rebuilds the term tables from `TAG_INDEX`, `SEARCH_APPEAR_KEYWORD_INDEX`
+/// and `TITLE_WORD_INDEX`. Used by the setup CLI for databases that predate the index.
+pub fn rebuild_index(db: &Database) -> Result<u64, DbError> {
+    let write_txn = db.begin_write()?;
+    let term_count;
+    {
+        write_txn.delete_table(FUZZY_TERMS)?;
+        write_txn.delete_table(FUZZY_TRIGRAMS)?;
+
+        let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+        for definition in [TAG_INDEX, SEARCH_APPEAR_KEYWORD_INDEX, TITLE_WORD_INDEX] {
+            let index = write_txn.open_table(definition)?;
+            for item_result in index.iter()? {
+                let (key, _) = item_result?;
+                *counts.entry(key.value().0.to_string()).or_insert(0) += 1;
+            }
+        }
+
+        let mut terms_table = write_txn.open_table(FUZZY_TERMS)?;
+        let mut trigram_table = write_txn.open_table(FUZZY_TRIGRAMS)?;
+        for (term, count) in &counts {
+            terms_table.insert(term.as_str(), *count)?;
+            for gram in trigrams(term) {
+                trigram_table.insert((gram.as_str(), term.as_str()), ())?;
+            }
+        }
+        term_count = counts.len() as u64;
+    }
+    write_txn.commit()?;
+    Ok(term_count)
+}
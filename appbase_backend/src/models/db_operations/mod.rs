@@ -1,3 +1,13 @@
 pub mod posts_db_operations;
 pub mod users_db_operations;
-pub mod advanced_db_manager_operations;
\ No newline at end of file
+pub mod advanced_db_manager_operations;
+pub mod fulltext_index_operations;
+pub mod tag_filter_operations;
+pub mod read_cache;
+pub mod suggest_operations;
+pub mod fuzzy_index_operations;
+pub mod outline_operations;
+pub mod view_operations;
+pub mod comment_operations;
+pub mod reaction_operations;
+pub mod webhook_operations;
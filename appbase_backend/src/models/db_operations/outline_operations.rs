@@ -0,0 +1,149 @@
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag};
+use redb::{Database, ReadableTable};
+use std::collections::HashMap;
+
+use super::posts_db_operations::{bump_publish_state, DbError, METADATA, POSTS};
+use super::read_cache;
+use crate::models::{PostMetadata, TocEntry};
+
+// Average adult silent reading speed.
+const READING_WORDS_PER_MINUTE: u32 = 200;
+
+/// What is derived from a post body when it is published.
+pub struct ContentOutline {
+    pub word_count: u32,
+    pub reading_time_minutes: u32,
+    pub toc: Vec<TocEntry>,
+}
+
+/// The Markdown extensions posts are rendered with. Outline and HTML rendering must
+/// parse with the same options so that headings line up with their anchors.
+pub fn markdown_options() -> Options {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options
+}
+
+/// GitHub-style anchor: lowercase letters and digits, with spaces and hyphens as `-`.
+fn slugify_heading(text: &str) -> String {
+    let mut anchor = String::new();
+    for c in text.trim().to_lowercase().chars() {
+        if c.is_alphanumeric() || c == '_' {
+            anchor.push(c);
+        } else if (c == ' ' || c == '-') && !anchor.ends_with('-') {
+            anchor.push('-');
+        }
+    }
+    let anchor = anchor.trim_matches('-');
+    if anchor.is_empty() { "section".to_string() } else { anchor.to_string() }
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+/// Counts words and collects the heading outline of a stored (entity-escaped) Markdown body.
+/// Anchors are unique within the post; repeats get `-1`, `-2`, ... appended.
+pub fn analyze_content(stored_markdown: &str) -> ContentOutline {
+    let decoded = html_escape::decode_html_entities(stored_markdown);
+    let mut word_count = 0u32;
+    let mut toc = Vec::new();
+    let mut seen_anchors: HashMap<String, u32> = HashMap::new();
+    let mut current_heading: Option<(u8, String)> = None;
+
+    for event in Parser::new_ext(&decoded, markdown_options()) {
+        match event {
+            Event::Start(Tag::Heading(level, _, _)) => current_heading = Some((heading_level(level), String::new())),
+            Event::End(Tag::Heading(..)) => {
+                if let Some((level, text)) = current_heading.take() {
+                    let text = text.trim();
+                    let base = slugify_heading(text);
+                    let repeats = seen_anchors.entry(base.clone()).or_insert(0);
+                    let anchor = if *repeats == 0 { base } else { format!("{}-{}", base, repeats) };
+                    *repeats += 1;
+                    // Stored escaped, like titles and summaries: API clients may insert it as HTML.
+                    let text = html_escape::encode_text(text).into_owned();
+                    toc.push(TocEntry { level, text, anchor });
+                }
+            }
+            Event::Text(t) | Event::Code(t) => {
+                word_count += t.split_whitespace().count() as u32;
+                if let Some((_, heading_text)) = current_heading.as_mut() {
+                    heading_text.push_str(&t);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ContentOutline {
+        word_count,
+        reading_time_minutes: word_count.div_ceil(READING_WORDS_PER_MINUTE).max(1),
+        toc,
+    }
+}
+
+/// Stores the derived fields of `content` in the post's metadata.
+pub fn apply_outline(metadata: &mut PostMetadata, content: &str) {
+    store_outline(metadata, analyze_content(content));
+}
+
+fn store_outline(metadata: &mut PostMetadata, outline: ContentOutline) {
+    metadata.word_count = Some(outline.word_count);
+    metadata.reading_time_minutes = Some(outline.reading_time_minutes);
+    metadata.toc = outline.toc;
+}
+
+/// Computes word count, reading time and outline for published posts that don't have them
+/// or whose stored outline differs from the current analysis (e.g. unescaped heading text).
+/// Returns how many posts were updated. Used by the setup CLI for posts published earlier.
+pub fn backfill_outlines(db: &Database) -> Result<u64, DbError> {
+    let write_txn = db.begin_write()?;
+    let mut updated = 0u64;
+    {
+        let stale: Vec<([u8; 16], PostMetadata, ContentOutline)> = {
+            let posts_table = write_txn.open_table(POSTS)?;
+            let metadata_table = write_txn.open_table(METADATA)?;
+            let mut stale = Vec::new();
+            for item_result in metadata_table.iter()? {
+                let (id_guard, meta_guard) = item_result?;
+                let post_id = *id_guard.value();
+                if let Ok(metadata) = serde_json::from_str::<PostMetadata>(meta_guard.value()) {
+                    if let Some(content_guard) = posts_table.get(&post_id)? {
+                        let outline = analyze_content(content_guard.value());
+                        if metadata.word_count.is_none() || metadata.toc != outline.toc {
+                            stale.push((post_id, metadata, outline));
+                        }
+                    }
+                }
+            }
+            stale
+        };
+
+        let mut metadata_table = write_txn.open_table(METADATA)?;
+        for (post_id, mut metadata, outline) in stale {
+            store_outline(&mut metadata, outline);
+            let metadata_json = serde_json::to_string(&metadata)?;
+            metadata_table.insert(&post_id, metadata_json.as_str())?;
+            updated += 1;
+        }
+    }
+    if updated > 0 {
+        bump_publish_state::<DbError>(&write_txn)?;
+    }
+    write_txn.commit()?;
+    if updated > 0 {
+        read_cache::invalidate_all();
+    }
+    Ok(updated)
+}
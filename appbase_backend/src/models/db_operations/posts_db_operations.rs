@@ -1,10 +1,12 @@
-use redb::{Database, ReadableTable, TableDefinition, CommitError, StorageError, TableError, TransactionError};
+use redb::{Database, ReadableTable, TableDefinition, WriteTransaction, CommitError, StorageError, TableError, TransactionError};
 use rusqlite::{params, Connection};
-use crate::models::{FullPost, PostMetadata, PostSummary};
-use crate::models::db_operations::users_db_operations;
+use crate::models::{ArchiveMonth, FullPost, PostMetadata, PostSummary, TagTreeNode};
+use crate::models::db_operations::{comment_operations, fulltext_index_operations, fuzzy_index_operations, outline_operations, reaction_operations, suggest_operations, users_db_operations, view_operations};
+use crate::models::db_operations::read_cache::{self, CacheKey};
 use uuid::Uuid;
-use chrono::Utc;
-use std::collections::HashSet;
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use std::collections::{HashMap, HashSet};
+use std::ops::Bound;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -37,11 +39,253 @@ pub const AVAILABLE_TAGS: TableDefinition<&str, ()> = TableDefinition::new("avai
 pub const CHRONOLOGICAL_INDEX: TableDefinition<(i64, &[u8; 16]), ()> = TableDefinition::new("chronological_index");
 
 
+// NEW: Change tracking for the published tables ("revision" counter, "last_modified" unix time).
+// Bumped in the same transaction as every change to published posts or available tags,
+// so it can serve as a cheap validator for list responses.
+pub const PUBLISH_STATE: TableDefinition<&str, i64> = TableDefinition::new("publish_state");
+const PUBLISH_REVISION_KEY: &str = "revision";
+const PUBLISH_LAST_MODIFIED_KEY: &str = "last_modified";
+
+
+// NEW: Slugs. SLUG_INDEX maps every slug a post ever had to its id (old ones are
+// answered with a redirect); POST_SLUGS is the reverse index used for cleanup.
+pub const SLUG_INDEX: TableDefinition<&str, &[u8; 16]> = TableDefinition::new("slug_index");
+pub const POST_SLUGS: TableDefinition<(&[u8; 16], &str), ()> = TableDefinition::new("post_slugs");
+const MAX_SLUG_LENGTH: usize = 80;
+
+
 // --- Tables for PENDING posts ---
 pub const PENDING_POSTS: TableDefinition<&[u8; 16], &str> = TableDefinition::new("pending_posts");
 pub const PENDING_METADATA: TableDefinition<&[u8; 16], &str> = TableDefinition::new("pending_metadata");
 
 
+/// A position inside one of the `(negated timestamp, uuid)` keyed indices.
+/// Used for keyset pagination: the next page starts with a range seek right
+/// after this key instead of skipping over every earlier entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostCursor {
+    pub timestamp: i64,
+    pub post_id: [u8; 16],
+}
+
+impl PostCursor {
+    /// Encodes the cursor as an opaque hex string for API clients.
+    pub fn encode(&self) -> String {
+        let mut bytes = [0u8; 24];
+        bytes[..8].copy_from_slice(&self.timestamp.to_be_bytes());
+        bytes[8..].copy_from_slice(&self.post_id);
+        hex::encode(bytes)
+    }
+
+    /// Decodes a cursor produced by `encode`. Returns `None` for anything malformed.
+    pub fn decode(raw: &str) -> Option<Self> {
+        let bytes = hex::decode(raw).ok()?;
+        if bytes.len() != 24 {
+            return None;
+        }
+        let mut timestamp_bytes = [0u8; 8];
+        timestamp_bytes.copy_from_slice(&bytes[..8]);
+        let mut post_id = [0u8; 16];
+        post_id.copy_from_slice(&bytes[8..]);
+        Some(PostCursor { timestamp: i64::from_be_bytes(timestamp_bytes), post_id })
+    }
+}
+
+/// Records a change to the published data inside the caller's write transaction.
+/// Generic over the error type so other db_operations modules can call it too.
+pub fn bump_publish_state<E>(txn: &WriteTransaction) -> Result<(), E>
+where
+    E: From<TableError> + From<StorageError>,
+{
+    let mut state = txn.open_table(PUBLISH_STATE)?;
+    let revision = state.get(PUBLISH_REVISION_KEY)?.map(|g| g.value()).unwrap_or(0);
+    state.insert(PUBLISH_REVISION_KEY, revision + 1)?;
+    state.insert(PUBLISH_LAST_MODIFIED_KEY, Utc::now().timestamp())?;
+    Ok(())
+}
+
+/// Bumps the publish state on its own, for changes that live outside posts.db but still
+/// show up in published responses (e.g. author profiles).
+pub fn touch_publish_state(db: &Database) -> Result<(), DbError> {
+    let write_txn = db.begin_write()?;
+    bump_publish_state::<DbError>(&write_txn)?;
+    write_txn.commit()?;
+    Ok(())
+}
+
+/// Returns the current publish revision and the time of the last change, if any.
+pub fn read_publish_state(db: &Database) -> Result<(i64, Option<DateTime<Utc>>), DbError> {
+    let read_txn = db.begin_read()?;
+    let state = read_txn.open_table(PUBLISH_STATE)?;
+    let revision = state.get(PUBLISH_REVISION_KEY)?.map(|g| g.value()).unwrap_or(0);
+    let last_modified = state
+        .get(PUBLISH_LAST_MODIFIED_KEY)?
+        .and_then(|g| DateTime::from_timestamp(g.value(), 0));
+    Ok((revision, last_modified))
+}
+
+/// Turns a title into a URL slug: lowercase alphanumerics separated by single dashes.
+/// Titles are stored entity-escaped, so they are decoded first: "Tom &amp; Jerry" gives
+/// `tom-jerry`, not `tom-amp-jerry`.
+pub fn slugify(title: &str) -> String {
+    let title = html_escape::decode_html_entities(title);
+    let mut slug = String::new();
+    let mut pending_dash = false;
+    for c in title.chars().flat_map(|c| c.to_lowercase()) {
+        if c.is_alphanumeric() {
+            if pending_dash && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_dash = false;
+            slug.push(c);
+        } else {
+            pending_dash = true;
+        }
+        if slug.chars().count() >= MAX_SLUG_LENGTH {
+            break;
+        }
+    }
+    if slug.is_empty() {
+        "post".to_string()
+    } else {
+        slug
+    }
+}
+
+/// True if `slug` is `base-N`, the form `assign_slug` gives a title whose bare slug is taken.
+fn has_dedupe_suffix(slug: &str, base: &str) -> bool {
+    slug.strip_prefix(base)
+        .and_then(|rest| rest.strip_prefix('-'))
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Picks the slug for a post whose title is `title`, inside the caller's write transaction.
+/// The current slug is kept while it still matches the title, either exactly or as
+/// `base-N` while another post owns `base`; otherwise a new unique slug is registered
+/// and the old one stays in `SLUG_INDEX` so it can redirect.
+fn assign_slug(
+    txn: &WriteTransaction,
+    post_id_bytes: &[u8; 16],
+    title: &str,
+    current_slug: Option<&str>,
+) -> Result<String, DbError> {
+    let base = slugify(title);
+    let mut slug_index = txn.open_table(SLUG_INDEX)?;
+    let mut post_slugs = txn.open_table(POST_SLUGS)?;
+
+    if let Some(current) = current_slug {
+        if current == base {
+            return Ok(current.to_string());
+        }
+        if has_dedupe_suffix(current, &base) {
+            let base_owner = slug_index.get(base.as_str())?.map(|g| *g.value());
+            if base_owner.is_some_and(|owner_id| owner_id != *post_id_bytes) {
+                return Ok(current.to_string());
+            }
+        }
+    }
+
+    let mut candidate = base.clone();
+    let mut n = 2;
+    loop {
+        let owner = slug_index.get(candidate.as_str())?.map(|g| *g.value());
+        match owner {
+            // Free, or a slug this post used before: take it.
+            None => break,
+            Some(owner_id) if owner_id == *post_id_bytes => break,
+            Some(_) => {
+                candidate = format!("{}-{}", base, n);
+                n += 1;
+            }
+        }
+    }
+
+    slug_index.insert(candidate.as_str(), post_id_bytes)?;
+    post_slugs.insert((post_id_bytes, candidate.as_str()), ())?;
+    Ok(candidate)
+}
+
+/// Removes every slug (current and historical) of a deleted post so they can be reused.
+fn remove_slugs(txn: &WriteTransaction, post_id_bytes: &[u8; 16]) -> Result<(), DbError> {
+    let mut slug_index = txn.open_table(SLUG_INDEX)?;
+    let mut post_slugs = txn.open_table(POST_SLUGS)?;
+
+    let start: (&[u8; 16], &str) = (post_id_bytes, "");
+    let mut slugs = Vec::new();
+    for item_result in post_slugs.range::<(&[u8; 16], &str)>((Bound::Included(start), Bound::Unbounded))? {
+        let (key, _value) = item_result?;
+        let (owner_id, slug) = key.value();
+        if owner_id != post_id_bytes {
+            break;
+        }
+        slugs.push(slug.to_string());
+    }
+    for slug in &slugs {
+        slug_index.remove(slug.as_str())?;
+        post_slugs.remove((post_id_bytes, slug.as_str()))?;
+    }
+    Ok(())
+}
+
+/// Looks a slug up. Returns the post id and the post's current slug, which differs
+/// from `slug` when the title changed since (the caller should redirect).
+pub fn resolve_slug(db: &Database, slug: &str) -> Result<Option<(String, Option<String>)>, DbError> {
+    read_cache::cached(CacheKey::Query(format!("slug{:?}", slug)), || load_resolve_slug(db, slug))
+}
+
+fn load_resolve_slug(db: &Database, slug: &str) -> Result<Option<(String, Option<String>)>, DbError> {
+    let read_txn = db.begin_read()?;
+    let slug_index = read_txn.open_table(SLUG_INDEX)?;
+    let metadata_table = read_txn.open_table(METADATA)?;
+
+    let post_id_bytes = match slug_index.get(slug)? {
+        Some(guard) => *guard.value(),
+        None => return Ok(None),
+    };
+    let metadata: PostMetadata = match metadata_table.get(&post_id_bytes)? {
+        Some(guard) => serde_json::from_str(guard.value())?,
+        None => return Ok(None),
+    };
+    Ok(Some((Uuid::from_bytes(post_id_bytes).to_string(), metadata.slug)))
+}
+
+/// Gives every published post without a slug one. Returns how many were assigned.
+/// Used by the setup CLI for posts approved before slugs existed.
+pub fn backfill_slugs(db: &Database) -> Result<u64, DbError> {
+    let write_txn = db.begin_write()?;
+    let mut assigned = 0u64;
+    {
+        let missing: Vec<([u8; 16], PostMetadata)> = {
+            let metadata_table = write_txn.open_table(METADATA)?;
+            let mut missing = Vec::new();
+            for item_result in metadata_table.iter()? {
+                let (id_guard, meta_guard) = item_result?;
+                if let Ok(metadata) = serde_json::from_str::<PostMetadata>(meta_guard.value()) {
+                    if metadata.slug.is_none() {
+                        missing.push((*id_guard.value(), metadata));
+                    }
+                }
+            }
+            missing
+        };
+
+        for (post_id_bytes, mut metadata) in missing {
+            metadata.slug = Some(assign_slug(&write_txn, &post_id_bytes, &metadata.title, None)?);
+            let metadata_json = serde_json::to_string(&metadata)?;
+            write_txn.open_table(METADATA)?.insert(&post_id_bytes, metadata_json.as_str())?;
+            assigned += 1;
+        }
+        if assigned > 0 {
+            bump_publish_state::<DbError>(&write_txn)?;
+        }
+    }
+    write_txn.commit()?;
+    if assigned > 0 {
+        read_cache::invalidate_all();
+    }
+    Ok(assigned)
+}
+
 fn generate_all_tags(tags_str: &str) -> HashSet<String> {
     let mut tags = HashSet::new();
     let initial_tags: Vec<String> = tags_str.split(',')
@@ -110,6 +354,10 @@ pub fn create_pending_post(
         search_keywords: Some(search_keywords),
         cover_image: cover_image.map(|s| s.to_string()),
         has_call_to_action,
+        slug: None,
+        word_count: None,
+        reading_time_minutes: None,
+        toc: Vec::new(),
     };
     let metadata_json = serde_json::to_string(&metadata)?;
 
@@ -189,6 +437,10 @@ pub fn update_pending_post(
             search_keywords: Some(new_search_keywords),
             cover_image: cover_image.map(|s| s.to_string()),
             has_call_to_action,
+            slug: old_meta.slug.clone(), // Re-evaluated against the new title on approval
+            word_count: None,
+            reading_time_minutes: None,
+            toc: Vec::new(),
         };
         let new_meta_json = serde_json::to_string(&new_meta)?;
         
@@ -217,6 +469,7 @@ pub fn read_pending_post(db: &Database, id: &str) -> Option<FullPost> {
                     id: id.to_string(),
                     content,
                     metadata,
+                    reactions: None,
                 });
             }
         }
@@ -234,7 +487,7 @@ pub fn read_all_pending_post_summaries_paginated(db: &Database, limit: u32, offs
             let post_uuid = Uuid::from_bytes(*id_bytes.value());
             serde_json::from_str::<PostMetadata>(meta_str.value())
                 .ok()
-                .map(|metadata| PostSummary { id: post_uuid.to_string(), metadata })
+                .map(|metadata| PostSummary { id: post_uuid.to_string(), metadata, author: None })
         }).collect();
 
     // Sort in memory (unavoidable without a dedicated index for pending posts)
@@ -250,6 +503,13 @@ pub fn read_all_pending_post_summaries_paginated(db: &Database, limit: u32, offs
 }
 
 
+pub fn count_pending_posts(db: &Database) -> Result<u64, DbError> {
+    let read_txn = db.begin_read()?;
+    let metadata_table = read_txn.open_table(PENDING_METADATA)?;
+    Ok(metadata_table.len()?)
+}
+
+
 pub fn read_pending_post_summaries_by_user(
     db: &Database,
     conn: &Connection,
@@ -270,7 +530,7 @@ pub fn read_pending_post_summaries_by_user(
             let post_id_bytes = post_uuid.into_bytes();
             if let Ok(Some(meta_guard)) = metadata_table.get(&post_id_bytes) {
                 if let Ok(metadata) = serde_json::from_str(meta_guard.value()) {
-                    return Some(PostSummary { id: id_str, metadata });
+                    return Some(PostSummary { id: id_str, metadata, author: None });
                 }
             }
         }
@@ -288,7 +548,7 @@ pub fn approve_post(db: &Database, conn: &Connection, post_id: &str) -> Result<(
     let post_id_bytes = post_uuid.into_bytes();
 
     // 1. Read the pending post data
-    let (content, metadata) = {
+    let (content, mut metadata) = {
         let read_txn = db.begin_read()?;
         let pending_posts_table = read_txn.open_table(PENDING_POSTS)?;
         let pending_metadata_table = read_txn.open_table(PENDING_METADATA)?;
@@ -323,6 +583,8 @@ pub fn approve_post(db: &Database, conn: &Connection, post_id: &str) -> Result<(
             let all_index_tags = generate_all_tags(&metadata.tags.join(", "));
             let index_keywords = process_keywords(&(metadata.search_keywords.clone().unwrap_or_default()).join(", "));
 
+            metadata.slug = Some(assign_slug(&write_txn, &post_id_bytes, &metadata.title, metadata.slug.as_deref())?);
+            outline_operations::apply_outline(&mut metadata, &content);
             let metadata_json = serde_json::to_string(&metadata)?;
             posts_table.insert(&post_id_bytes, content.as_str())?;
             metadata_table.insert(&post_id_bytes, metadata_json.as_str())?;
@@ -330,13 +592,23 @@ pub fn approve_post(db: &Database, conn: &Connection, post_id: &str) -> Result<(
             let timestamp = -metadata.created_at.timestamp();
             chrono_index.insert((timestamp, &post_id_bytes), ())?; // NEW
 
+            // Terms of index entries that did not exist yet, for the fuzzy search vocabulary.
+            let mut new_terms: Vec<String> = Vec::new();
             for tag in &all_index_tags {
-                tag_index.insert((tag.as_str(), timestamp, &post_id_bytes), ())?;
+                if tag_index.insert((tag.as_str(), timestamp, &post_id_bytes), ())?.is_none() {
+                    new_terms.push(tag.clone());
+                }
             }
             for keyword in &index_keywords {
-                keyword_index.insert((keyword.as_str(), timestamp, &post_id_bytes), ())?;
+                if keyword_index.insert((keyword.as_str(), timestamp, &post_id_bytes), ())?.is_none() {
+                    new_terms.push(keyword.clone());
+                }
             }
+            new_terms.extend(suggest_operations::index_title(&write_txn, &post_id_bytes, &metadata.title, timestamp)?);
+            fuzzy_index_operations::add_terms(&write_txn, &new_terms)?;
         }
+        fulltext_index_operations::index_post(&write_txn, &post_id_bytes, &metadata.title, &metadata.summary, &content)?;
+        bump_publish_state::<DbError>(&write_txn)?;
         write_txn.commit()?;
         Ok(())
     })();
@@ -347,6 +619,7 @@ pub fn approve_post(db: &Database, conn: &Connection, post_id: &str) -> Result<(
         conn.execute("DELETE FROM post_ownership WHERE post_id = ?1", [post_id])?;
         return Err(e);
     }
+    read_cache::invalidate_post(&post_id_bytes);
 
     // 4. Delete from pending tables (DB and ownership)
     delete_pending_post(db, post_id)?;
@@ -373,6 +646,11 @@ pub fn move_published_to_pending(db: &Database, post_id: &str) -> Result<(), DbE
         let content = posts_table.get(&post_id_bytes)?.ok_or(DbError::NotFound(post_id.to_string()))?.value().to_string();
         let metadata = metadata_table.get(&post_id_bytes)?.ok_or(DbError::NotFound(post_id.to_string()))?.value().to_string();
 
+        if let Ok(meta) = serde_json::from_str::<PostMetadata>(&metadata) {
+            let removed_words = suggest_operations::remove_title(&write_txn, &post_id_bytes, &meta.title, -meta.created_at.timestamp())?;
+            fuzzy_index_operations::remove_terms(&write_txn, &removed_words)?;
+        }
+
         // 2. Write them to the pending tables.
         pending_posts_table.insert(&post_id_bytes, content.as_str())?;
         pending_metadata_table.insert(&post_id_bytes, metadata.as_str())?;
@@ -383,7 +661,10 @@ pub fn move_published_to_pending(db: &Database, post_id: &str) -> Result<(), DbE
         posts_table.remove(&post_id_bytes)?;
         metadata_table.remove(&post_id_bytes)?;
     }
+    fulltext_index_operations::remove_post(&write_txn, &post_id_bytes)?;
+    bump_publish_state::<DbError>(&write_txn)?;
     write_txn.commit()?;
+    read_cache::invalidate_post(&post_id_bytes);
     Ok(())
 }
 
@@ -393,6 +674,13 @@ pub fn move_published_to_pending(db: &Database, post_id: &str) -> Result<(), DbE
 // ====================================================================
 
 pub fn read_post(db: &Database, id: &str) -> Option<FullPost> {
+    let post_id_bytes = Uuid::parse_str(id).ok()?.into_bytes();
+    read_cache::cached(CacheKey::Post(post_id_bytes), || Ok::<_, ()>(load_post(db, id)))
+        .ok()
+        .flatten()
+}
+
+fn load_post(db: &Database, id: &str) -> Option<FullPost> {
     let post_uuid = Uuid::parse_str(id).ok()?;
     let post_id_bytes = post_uuid.into_bytes();
 
@@ -410,6 +698,7 @@ pub fn read_post(db: &Database, id: &str) -> Option<FullPost> {
                     id: id.to_string(),
                     content,
                     metadata,
+                    reactions: None,
                 });
             }
         }
@@ -445,15 +734,23 @@ pub fn update_post(
         
         let timestamp = -old_meta.created_at.timestamp();
         
+        // Terms of removed and added index entries, for the fuzzy search vocabulary.
+        let mut removed_terms: Vec<String> = Vec::new();
+        let mut added_terms: Vec<String> = Vec::new();
+
         let old_tags_to_remove = generate_all_tags(&old_meta.tags.join(", "));
         for tag in &old_tags_to_remove {
-            tag_index.remove((tag.as_str(), timestamp, &post_id_bytes))?;
+            if tag_index.remove((tag.as_str(), timestamp, &post_id_bytes))?.is_some() {
+                removed_terms.push(tag.clone());
+            }
         }
         
         if let Some(old_keywords) = old_meta.search_keywords.as_deref() {
             let old_index_keywords = process_keywords(&old_keywords.join(", "));
             for keyword in &old_index_keywords {
-                keyword_index.remove((keyword.as_str(), timestamp, &post_id_bytes))?;
+                if keyword_index.remove((keyword.as_str(), timestamp, &post_id_bytes))?.is_some() {
+                    removed_terms.push(keyword.clone());
+                }
             }
         }
 
@@ -467,7 +764,7 @@ pub fn update_post(
             .filter(|s| !s.is_empty())
             .collect();
 
-        let new_meta = PostMetadata {
+        let mut new_meta = PostMetadata {
             title: title.to_string(),
             created_at: old_meta.created_at,
             last_updated_at: Some(Utc::now()),
@@ -476,7 +773,12 @@ pub fn update_post(
             search_keywords: Some(new_search_keywords),
             cover_image: cover_image.map(|s| s.to_string()),
             has_call_to_action,
+            slug: Some(assign_slug(&write_txn, &post_id_bytes, title, old_meta.slug.as_deref())?),
+            word_count: None,
+            reading_time_minutes: None,
+            toc: Vec::new(),
         };
+        outline_operations::apply_outline(&mut new_meta, content);
         let new_meta_json = serde_json::to_string(&new_meta)?;
         
         let new_tags_to_add = generate_all_tags(tags_str);
@@ -486,14 +788,26 @@ pub fn update_post(
         metadata_table.insert(&post_id_bytes, new_meta_json.as_str())?;
         
         for tag in &new_tags_to_add {
-            tag_index.insert((tag.as_str(), timestamp, &post_id_bytes), ())?;
+            if tag_index.insert((tag.as_str(), timestamp, &post_id_bytes), ())?.is_none() {
+                added_terms.push(tag.clone());
+            }
         }
         
         for keyword in &new_index_keywords {
-            keyword_index.insert((keyword.as_str(), timestamp, &post_id_bytes), ())?;
+            if keyword_index.insert((keyword.as_str(), timestamp, &post_id_bytes), ())?.is_none() {
+                added_terms.push(keyword.clone());
+            }
         }
+
+        removed_terms.extend(suggest_operations::remove_title(&write_txn, &post_id_bytes, &old_meta.title, timestamp)?);
+        added_terms.extend(suggest_operations::index_title(&write_txn, &post_id_bytes, title, timestamp)?);
+        fuzzy_index_operations::add_terms(&write_txn, &added_terms)?;
+        fuzzy_index_operations::remove_terms(&write_txn, &removed_terms)?;
     }
+    fulltext_index_operations::index_post(&write_txn, &post_id_bytes, title, summary, content)?;
+    bump_publish_state::<DbError>(&write_txn)?;
     write_txn.commit()?;
+    read_cache::invalidate_post(&post_id_bytes);
     Ok(())
 }
 
@@ -519,23 +833,37 @@ pub fn delete_post(db: &Database, conn: &Connection, post_id: &str) -> Result<()
             let timestamp = -meta.created_at.timestamp();
             chrono_index.remove((timestamp, &post_id_bytes))?; // NEW
 
+            let mut removed_terms: Vec<String> = Vec::new();
             let all_tags_to_remove = generate_all_tags(&meta.tags.join(", "));
             for tag in &all_tags_to_remove {
-                 tag_index.remove((tag.as_str(), timestamp, &post_id_bytes))?;
+                 if tag_index.remove((tag.as_str(), timestamp, &post_id_bytes))?.is_some() {
+                     removed_terms.push(tag.clone());
+                 }
             }
             
             if let Some(keywords) = meta.search_keywords.as_deref() {
                 let index_keywords_to_remove = process_keywords(&keywords.join(", "));
                 for keyword in &index_keywords_to_remove {
-                    keyword_index.remove((keyword.as_str(), timestamp, &post_id_bytes))?;
+                    if keyword_index.remove((keyword.as_str(), timestamp, &post_id_bytes))?.is_some() {
+                        removed_terms.push(keyword.clone());
+                    }
                 }
             }
+            removed_terms.extend(suggest_operations::remove_title(&write_txn, &post_id_bytes, &meta.title, timestamp)?);
+            fuzzy_index_operations::remove_terms(&write_txn, &removed_terms)?;
         }
         
         posts_table.remove(&post_id_bytes)?;
         metadata_table.remove(&post_id_bytes)?;
     }
+    fulltext_index_operations::remove_post(&write_txn, &post_id_bytes)?;
+    remove_slugs(&write_txn, &post_id_bytes)?;
+    view_operations::remove_post_views(&write_txn, &post_id_bytes)?;
+    comment_operations::remove_post_comments(&write_txn, &post_id_bytes)?;
+    reaction_operations::remove_post_reactions(&write_txn, &post_id_bytes)?;
+    bump_publish_state::<DbError>(&write_txn)?;
     write_txn.commit()?;
+    read_cache::invalidate_post(&post_id_bytes);
     
     Ok(())
 }
@@ -545,6 +873,11 @@ pub fn delete_post(db: &Database, conn: &Connection, post_id: &str) -> Result<()
 
 // UPDATED: Now uses the chronological index for performance
 pub fn read_latest_post_summaries(db: &Database, limit: u32, offset: u32) -> Result<Vec<PostSummary>, DbError> {
+    let key = CacheKey::Query(format!("latest{:?}", (limit, offset)));
+    read_cache::cached(key, || load_latest_post_summaries(db, limit, offset))
+}
+
+fn load_latest_post_summaries(db: &Database, limit: u32, offset: u32) -> Result<Vec<PostSummary>, DbError> {
     let read_txn = db.begin_read()?;
     let chrono_index = read_txn.open_table(CHRONOLOGICAL_INDEX)?;
     let metadata_table = read_txn.open_table(METADATA)?;
@@ -561,6 +894,7 @@ pub fn read_latest_post_summaries(db: &Database, limit: u32, offset: u32) -> Res
                     serde_json::from_str(meta_str.value()).ok().map(|metadata| PostSummary {
                         id: post_uuid.to_string(),
                         metadata,
+                        author: None,
                     })
                 })
             })
@@ -569,11 +903,327 @@ pub fn read_latest_post_summaries(db: &Database, limit: u32, offset: u32) -> Res
     Ok(posts)
 }
 
+/// Returns `(post id, last modification time)` for published posts, walking
+/// `CHRONOLOGICAL_INDEX` oldest first so a given offset keeps addressing the same posts
+/// as new ones are published. Used for sitemaps.
+pub fn read_post_lastmods_oldest_first(
+    db: &Database,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<(String, DateTime<Utc>)>, DbError> {
+    let read_txn = db.begin_read()?;
+    let chrono_index = read_txn.open_table(CHRONOLOGICAL_INDEX)?;
+    let metadata_table = read_txn.open_table(METADATA)?;
+
+    let mut entries = Vec::new();
+    for item_result in chrono_index.iter()?.rev().skip(offset as usize).take(limit as usize) {
+        let (key, _value) = item_result?;
+        let post_id_bytes = key.value().1;
+        if let Some(lastmod) = read_lastmod(&metadata_table, post_id_bytes)? {
+            entries.push((Uuid::from_bytes(*post_id_bytes).to_string(), lastmod));
+        }
+    }
+    Ok(entries)
+}
+
+/// The newest modification time of each `page_size`-post page that
+/// `read_post_lastmods_oldest_first` would return, in page order, from a single walk of
+/// `CHRONOLOGICAL_INDEX`. Used for the sitemap index.
+pub fn read_page_lastmods_oldest_first(db: &Database, page_size: u32) -> Result<Vec<Option<DateTime<Utc>>>, DbError> {
+    let read_txn = db.begin_read()?;
+    let chrono_index = read_txn.open_table(CHRONOLOGICAL_INDEX)?;
+    let metadata_table = read_txn.open_table(METADATA)?;
+
+    let page_size = page_size.max(1) as usize;
+    let mut pages: Vec<Option<DateTime<Utc>>> = Vec::new();
+    for (position, item_result) in chrono_index.iter()?.rev().enumerate() {
+        let (key, _value) = item_result?;
+        if position % page_size == 0 {
+            pages.push(None);
+        }
+        let lastmod = read_lastmod(&metadata_table, key.value().1)?;
+        if let Some(newest) = pages.last_mut() {
+            *newest = (*newest).max(lastmod);
+        }
+    }
+    Ok(pages)
+}
+
+fn read_lastmod(
+    metadata_table: &redb::ReadOnlyTable<&[u8; 16], &str>,
+    post_id_bytes: &[u8; 16],
+) -> Result<Option<DateTime<Utc>>, DbError> {
+    Ok(metadata_table
+        .get(post_id_bytes)?
+        .and_then(|meta_guard| serde_json::from_str::<PostMetadata>(meta_guard.value()).ok())
+        .map(|metadata| metadata.last_updated_at.unwrap_or(metadata.created_at)))
+}
+
+/// Number of published posts, taken from the length of `CHRONOLOGICAL_INDEX`.
+pub fn count_published_posts(db: &Database) -> Result<u64, DbError> {
+    read_cache::cached(CacheKey::Query("count".to_string()), || load_count_published_posts(db))
+}
+
+fn load_count_published_posts(db: &Database) -> Result<u64, DbError> {
+    let read_txn = db.begin_read()?;
+    let chrono_index = read_txn.open_table(CHRONOLOGICAL_INDEX)?;
+    Ok(chrono_index.len()?)
+}
+
+/// `CHRONOLOGICAL_INDEX` key bounds for posts created in `[from, until)`.
+/// Keys hold the negated timestamp, so the newer bound comes first.
+fn chronological_range(from: DateTime<Utc>, until: DateTime<Utc>) -> ((i64, [u8; 16]), (i64, [u8; 16])) {
+    ((-until.timestamp() + 1, [0u8; 16]), (-from.timestamp(), [255u8; 16]))
+}
+
+/// Reads the published posts created in `[from, until)`, newest first, by range-scanning
+/// `CHRONOLOGICAL_INDEX`. Returns the requested page together with the number of posts in the range.
+pub fn read_post_summaries_in_date_range(
+    db: &Database,
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+    limit: u32,
+    offset: u32,
+) -> Result<(Vec<PostSummary>, u64), DbError> {
+    let key = CacheKey::Query(format!("date_range{:?}", (from.timestamp(), until.timestamp(), limit, offset)));
+    read_cache::cached(key, || load_post_summaries_in_date_range(db, from, until, limit, offset))
+}
+
+fn load_post_summaries_in_date_range(
+    db: &Database,
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+    limit: u32,
+    offset: u32,
+) -> Result<(Vec<PostSummary>, u64), DbError> {
+    let read_txn = db.begin_read()?;
+    let chrono_index = read_txn.open_table(CHRONOLOGICAL_INDEX)?;
+    let metadata_table = read_txn.open_table(METADATA)?;
+
+    let (start, end) = chronological_range(from, until);
+    let mut ids = Vec::new();
+    for item_result in chrono_index.range((start.0, &start.1)..=(end.0, &end.1))? {
+        let (key, _value) = item_result?;
+        ids.push(*key.value().1);
+    }
+    let total = ids.len() as u64;
+
+    let mut posts = Vec::new();
+    for post_id in ids.iter().skip(offset as usize).take(limit as usize) {
+        if let Some(meta_guard) = metadata_table.get(post_id)? {
+            if let Ok(metadata) = serde_json::from_str::<PostMetadata>(meta_guard.value()) {
+                posts.push(PostSummary { id: Uuid::from_bytes(*post_id).to_string(), metadata, author: None });
+            }
+        }
+    }
+    Ok((posts, total))
+}
+
+/// Per-month published post counts (UTC), newest month first.
+/// Only the index keys are read; no metadata is loaded.
+pub fn read_archive_month_counts(db: &Database) -> Result<Vec<ArchiveMonth>, DbError> {
+    read_cache::cached(CacheKey::Query("archive_months".to_string()), || load_archive_month_counts(db))
+}
+
+fn load_archive_month_counts(db: &Database) -> Result<Vec<ArchiveMonth>, DbError> {
+    let read_txn = db.begin_read()?;
+    let chrono_index = read_txn.open_table(CHRONOLOGICAL_INDEX)?;
+
+    let mut months: Vec<ArchiveMonth> = Vec::new();
+    for item_result in chrono_index.iter()? {
+        let (key, _value) = item_result?;
+        let created_at = match Utc.timestamp_opt(-key.value().0, 0).single() {
+            Some(t) => t,
+            None => continue,
+        };
+        let (year, month) = (created_at.year(), created_at.month());
+        match months.last_mut() {
+            Some(last) if last.year == year && last.month == month => last.post_count += 1,
+            _ => months.push(ArchiveMonth { year, month, post_count: 1 }),
+        }
+    }
+    Ok(months)
+}
+
+/// Counts the entries for one term of a `(term, negated timestamp, uuid)` index
+/// by walking its key range. No metadata is loaded.
+fn count_term_index(
+    db: &Database,
+    index: TableDefinition<(&str, i64, &[u8; 16]), ()>,
+    term: &str,
+) -> Result<u64, DbError> {
+    let read_txn = db.begin_read()?;
+    let term_index = read_txn.open_table(index)?;
+
+    let lower_term = term.to_lowercase();
+    let start_key = (lower_term.as_str(), i64::MIN, &[0u8; 16]);
+    let end_key = (lower_term.as_str(), i64::MAX, &[255u8; 16]);
+
+    let mut count = 0u64;
+    for item_result in term_index.range(start_key..=end_key)? {
+        item_result?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+pub fn count_posts_by_tag(db: &Database, tag: &str) -> Result<u64, DbError> {
+    let key = CacheKey::Query(format!("tag_count{:?}", tag.to_lowercase()));
+    read_cache::cached(key, || load_count_posts_by_tag(db, tag))
+}
+
+fn load_count_posts_by_tag(db: &Database, tag: &str) -> Result<u64, DbError> {
+    count_term_index(db, TAG_INDEX, tag)
+}
+
+pub fn count_posts_by_keyword(db: &Database, keyword: &str) -> Result<u64, DbError> {
+    count_term_index(db, SEARCH_APPEAR_KEYWORD_INDEX, keyword)
+}
+
+/// Loads the summaries for a page of index keys. At most `limit` keys are used;
+/// if the iterator yields one more, the cursor of the last returned key is
+/// handed back so the caller can continue from there.
+fn collect_keyset_page<I>(
+    keys: I,
+    metadata_table: &redb::ReadOnlyTable<&[u8; 16], &str>,
+    limit: u32,
+) -> (Vec<PostSummary>, Option<PostCursor>)
+where
+    I: Iterator<Item = PostCursor>,
+{
+    let mut page_keys: Vec<PostCursor> = keys.take(limit as usize + 1).collect();
+    let has_more = page_keys.len() > limit as usize;
+    page_keys.truncate(limit as usize);
+
+    let next_cursor = if has_more { page_keys.last().copied() } else { None };
+
+    let posts = page_keys
+        .iter()
+        .filter_map(|key| {
+            metadata_table.get(&key.post_id).ok().flatten().and_then(|meta_str| {
+                let post_uuid = Uuid::from_bytes(key.post_id);
+                serde_json::from_str(meta_str.value()).ok().map(|metadata| PostSummary {
+                    id: post_uuid.to_string(),
+                    metadata,
+                    author: None,
+                })
+            })
+        })
+        .collect();
+
+    (posts, next_cursor)
+}
+
+/// Keyset-paginated version of `read_latest_post_summaries`.
+/// Starts right after `cursor` (or at the newest post when `None`).
+pub fn read_latest_post_summaries_after(
+    db: &Database,
+    cursor: Option<&PostCursor>,
+    limit: u32,
+) -> Result<(Vec<PostSummary>, Option<PostCursor>), DbError> {
+    let key = CacheKey::Query(format!("latest_after{:?}", (cursor, limit)));
+    read_cache::cached(key, || load_latest_post_summaries_after(db, cursor, limit))
+}
+
+fn load_latest_post_summaries_after(
+    db: &Database,
+    cursor: Option<&PostCursor>,
+    limit: u32,
+) -> Result<(Vec<PostSummary>, Option<PostCursor>), DbError> {
+    let read_txn = db.begin_read()?;
+    let chrono_index = read_txn.open_table(CHRONOLOGICAL_INDEX)?;
+    let metadata_table = read_txn.open_table(METADATA)?;
+
+    let start = match cursor {
+        Some(c) => Bound::Excluded((c.timestamp, &c.post_id)),
+        None => Bound::Unbounded,
+    };
+
+    let keys = chrono_index
+        .range((start, Bound::Unbounded))?
+        .filter_map(|item_result| item_result.ok())
+        .map(|(key, _value)| {
+            let (timestamp, post_id) = key.value();
+            PostCursor { timestamp, post_id: *post_id }
+        });
+
+    Ok(collect_keyset_page(keys, &metadata_table, limit))
+}
+
+/// Keyset pagination over any `(term, negated timestamp, uuid)` index,
+/// i.e. `TAG_INDEX` and `SEARCH_APPEAR_KEYWORD_INDEX`.
+fn read_term_index_after(
+    db: &Database,
+    index: TableDefinition<(&str, i64, &[u8; 16]), ()>,
+    term: &str,
+    cursor: Option<&PostCursor>,
+    limit: u32,
+) -> Result<(Vec<PostSummary>, Option<PostCursor>), DbError> {
+    let read_txn = db.begin_read()?;
+    let term_index = read_txn.open_table(index)?;
+    let metadata_table = read_txn.open_table(METADATA)?;
+
+    let lower_term = term.to_lowercase();
+    let start = match cursor {
+        Some(c) => Bound::Excluded((lower_term.as_str(), c.timestamp, &c.post_id)),
+        None => Bound::Included((lower_term.as_str(), i64::MIN, &[0u8; 16])),
+    };
+    let end = Bound::Included((lower_term.as_str(), i64::MAX, &[255u8; 16]));
+
+    let keys = term_index
+        .range((start, end))?
+        .filter_map(|item_result| item_result.ok())
+        .map(|(key, _value)| {
+            let (_, timestamp, post_id) = key.value();
+            PostCursor { timestamp, post_id: *post_id }
+        });
+
+    Ok(collect_keyset_page(keys, &metadata_table, limit))
+}
+
+pub fn read_post_summaries_by_tag_after(
+    db: &Database,
+    tag: &str,
+    cursor: Option<&PostCursor>,
+    limit: u32,
+) -> Result<(Vec<PostSummary>, Option<PostCursor>), DbError> {
+    let key = CacheKey::Query(format!("tag_after{:?}", (tag.to_lowercase(), cursor, limit)));
+    read_cache::cached(key, || load_post_summaries_by_tag_after(db, tag, cursor, limit))
+}
+
+fn load_post_summaries_by_tag_after(
+    db: &Database,
+    tag: &str,
+    cursor: Option<&PostCursor>,
+    limit: u32,
+) -> Result<(Vec<PostSummary>, Option<PostCursor>), DbError> {
+    read_term_index_after(db, TAG_INDEX, tag, cursor, limit)
+}
+
+pub fn read_post_summaries_by_keyword_after(
+    db: &Database,
+    keyword: &str,
+    cursor: Option<&PostCursor>,
+    limit: u32,
+) -> Result<(Vec<PostSummary>, Option<PostCursor>), DbError> {
+    read_term_index_after(db, SEARCH_APPEAR_KEYWORD_INDEX, keyword, cursor, limit)
+}
+
 pub fn read_post_summaries_by_tag(
     db: &Database,
     tag: &str,
     limit: u32,
     offset: u32,
+) -> Result<Vec<PostSummary>, DbError> {
+    let key = CacheKey::Query(format!("tag{:?}", (tag.to_lowercase(), limit, offset)));
+    read_cache::cached(key, || load_post_summaries_by_tag(db, tag, limit, offset))
+}
+
+fn load_post_summaries_by_tag(
+    db: &Database,
+    tag: &str,
+    limit: u32,
+    offset: u32,
 ) -> Result<Vec<PostSummary>, DbError> {
     let read_txn = db.begin_read()?;
     let tag_index = read_txn.open_table(TAG_INDEX)?;
@@ -595,6 +1245,7 @@ pub fn read_post_summaries_by_tag(
                     serde_json::from_str(meta_str.value()).ok().map(|metadata| PostSummary {
                         id: post_uuid.to_string(),
                         metadata,
+                        author: None,
                     })
                 })
             })
@@ -623,7 +1274,7 @@ pub fn read_post_summaries_by_user(
             let post_id_bytes = post_uuid.into_bytes();
             if let Ok(Some(meta_guard)) = metadata_table.get(&post_id_bytes) {
                 if let Ok(metadata) = serde_json::from_str(meta_guard.value()) {
-                    return Some(PostSummary { id: id_str, metadata });
+                    return Some(PostSummary { id: id_str, metadata, author: None });
                 }
             }
         }
@@ -689,6 +1340,7 @@ pub fn find_similar_posts(
                     matching_posts.push(PostSummary {
                         id: post_uuid.to_string(),
                         metadata,
+                        author: None,
                     });
                 }
             }
@@ -700,13 +1352,85 @@ pub fn find_similar_posts(
     Ok(matching_posts)
 }
 
+/// Builds the nested tag taxonomy from `TAG_INDEX` in a single pass.
+///
+/// `generate_all_tags` also indexes every segment of a path on its own ("a/b" adds
+/// "a", "b" and "a/b"). A plain tag is therefore only shown as a root if it is the
+/// first segment of some path, or if at least one post carries it other than as an
+/// inner segment, so "b" from "a/b" does not show up as a top-level category.
+pub fn read_tag_tree(db: &Database) -> Result<Vec<TagTreeNode>, DbError> {
+    read_cache::cached(CacheKey::Query("tag_tree".to_string()), || load_tag_tree(db))
+}
+
+fn load_tag_tree(db: &Database) -> Result<Vec<TagTreeNode>, DbError> {
+    let read_txn = db.begin_read()?;
+    let tag_index = read_txn.open_table(TAG_INDEX)?;
+
+    let mut posts_by_tag: HashMap<String, HashSet<[u8; 16]>> = HashMap::new();
+    for item_result in tag_index.iter()? {
+        let (key, _value) = item_result?;
+        let (tag, _timestamp, post_id) = key.value();
+        posts_by_tag.entry(tag.to_string()).or_default().insert(*post_id);
+    }
+
+    let mut first_segments: HashSet<String> = HashSet::new();
+    let mut inner_segment_posts: HashMap<String, HashSet<[u8; 16]>> = HashMap::new();
+    let mut children_of: HashMap<String, Vec<String>> = HashMap::new();
+    for (tag, posts) in &posts_by_tag {
+        if let Some((parent, _)) = tag.rsplit_once('/') {
+            children_of.entry(parent.to_string()).or_default().push(tag.clone());
+            let mut segments = tag.split('/');
+            if let Some(first) = segments.next() {
+                first_segments.insert(first.to_string());
+            }
+            for segment in segments {
+                inner_segment_posts.entry(segment.to_string()).or_default().extend(posts.iter().copied());
+            }
+        }
+    }
+
+    fn build_node(
+        path: &str,
+        posts_by_tag: &HashMap<String, HashSet<[u8; 16]>>,
+        children_of: &HashMap<String, Vec<String>>,
+    ) -> TagTreeNode {
+        let mut children: Vec<TagTreeNode> = children_of
+            .get(path)
+            .map(|paths| paths.iter().map(|child| build_node(child, posts_by_tag, children_of)).collect())
+            .unwrap_or_default();
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+        TagTreeNode {
+            name: path.rsplit('/').next().unwrap_or(path).to_string(),
+            path: path.to_string(),
+            post_count: posts_by_tag.get(path).map_or(0, |posts| posts.len() as u64),
+            children,
+        }
+    }
+
+    let mut roots: Vec<TagTreeNode> = posts_by_tag
+        .iter()
+        .filter(|(tag, posts)| {
+            !tag.contains('/')
+                && (first_segments.contains(*tag)
+                    || !inner_segment_posts
+                        .get(*tag)
+                        .is_some_and(|inner| posts.iter().all(|id| inner.contains(id))))
+        })
+        .map(|(tag, _)| build_node(tag, &posts_by_tag, &children_of))
+        .collect();
+    roots.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(roots)
+}
+
 pub fn add_available_tag(db: &Database, tag: &str) -> Result<(), DbError> {
     let write_txn = db.begin_write()?;
     {
         let mut available_tags_table = write_txn.open_table(AVAILABLE_TAGS)?;
         available_tags_table.insert(tag.trim().to_lowercase().as_str(), ())?; // NORMALIZE
     }
+    bump_publish_state::<DbError>(&write_txn)?;
     write_txn.commit()?;
+    read_cache::invalidate_queries();
     Ok(())
 }
 
@@ -716,11 +1440,17 @@ pub fn delete_available_tag(db: &Database, tag: &str) -> Result<(), DbError> {
         let mut available_tags_table = write_txn.open_table(AVAILABLE_TAGS)?;
         available_tags_table.remove(tag.trim().to_lowercase().as_str())?; // NORMALIZE
     }
+    bump_publish_state::<DbError>(&write_txn)?;
     write_txn.commit()?;
+    read_cache::invalidate_queries();
     Ok(())
 }
 
 pub fn get_all_available_tags(db: &Database) -> Result<Vec<String>, DbError> {
+    read_cache::cached(CacheKey::Query("available_tags".to_string()), || load_available_tags(db))
+}
+
+fn load_available_tags(db: &Database) -> Result<Vec<String>, DbError> {
     let read_txn = db.begin_read()?;
     let table = read_txn.open_table(AVAILABLE_TAGS)?;
     let tags: Vec<String> = table
@@ -732,6 +1462,14 @@ pub fn get_all_available_tags(db: &Database) -> Result<Vec<String>, DbError> {
 }
 
 pub fn read_post_summary_by_id(db: &Database, id: &str) -> Result<Option<PostSummary>, DbError> {
+    let post_id_bytes = match Uuid::parse_str(id) {
+        Ok(uuid) => uuid.into_bytes(),
+        Err(_) => return Ok(None),
+    };
+    read_cache::cached(CacheKey::PostSummary(post_id_bytes), || load_post_summary_by_id(db, id))
+}
+
+fn load_post_summary_by_id(db: &Database, id: &str) -> Result<Option<PostSummary>, DbError> {
     let post_uuid = match Uuid::parse_str(id) {
         Ok(uuid) => uuid,
         Err(_) => return Ok(None),
@@ -750,19 +1488,113 @@ pub fn read_post_summary_by_id(db: &Database, id: &str) -> Result<Option<PostSum
         Ok(Some(PostSummary {
             id: id.to_string(),
             metadata,
+            author: None,
         }))
     } else {
         Ok(None)
     }
 }
 
+// Scoring weights for related posts. A tag scores its depth ("a/b/c" = 3), so
+// specific paths count more than broad top-level tags.
+const RELATED_KEYWORD_WEIGHT: u32 = 2;
+// Only the newest entries of each tag/keyword range are considered, which keeps
+// very broad tags from dominating the cost of the lookup.
+const RELATED_CANDIDATES_PER_TERM: usize = 500;
+
+/// Finds published posts related to `post_id` by overlap on expanded tags and
+/// search keywords, best score first and newer first on ties.
+/// Works from `TAG_INDEX` / `SEARCH_APPEAR_KEYWORD_INDEX` ranges only.
+/// Returns `None` if the post itself is not published.
+pub fn read_related_post_summaries(
+    db: &Database,
+    post_id: &str,
+    limit: u32,
+) -> Result<Option<Vec<PostSummary>>, DbError> {
+    let post_uuid = match Uuid::parse_str(post_id) {
+        Ok(uuid) => uuid,
+        Err(_) => return Ok(None),
+    };
+    let post_id_bytes = post_uuid.into_bytes();
+
+    let read_txn = db.begin_read()?;
+    let metadata_table = read_txn.open_table(METADATA)?;
+    let tag_index = read_txn.open_table(TAG_INDEX)?;
+    let keyword_index = read_txn.open_table(SEARCH_APPEAR_KEYWORD_INDEX)?;
+
+    let metadata: PostMetadata = match metadata_table.get(&post_id_bytes)? {
+        Some(guard) => serde_json::from_str(guard.value())?,
+        None => return Ok(None),
+    };
+
+    // (is a tag, term, weight)
+    let mut weighted_terms: Vec<(bool, String, u32)> = Vec::new();
+    for tag in generate_all_tags(&metadata.tags.join(", ")) {
+        let depth = tag.split('/').count() as u32;
+        weighted_terms.push((true, tag, depth));
+    }
+    for keyword in process_keywords(&metadata.search_keywords.clone().unwrap_or_default().join(", ")) {
+        weighted_terms.push((false, keyword, RELATED_KEYWORD_WEIGHT));
+    }
+
+    // post id -> (score, negated timestamp)
+    let mut scores: HashMap<[u8; 16], (u32, i64)> = HashMap::new();
+    for (is_tag, term, weight) in &weighted_terms {
+        let table = if *is_tag { &tag_index } else { &keyword_index };
+        let start_key = (term.as_str(), i64::MIN, &[0u8; 16]);
+        let end_key = (term.as_str(), i64::MAX, &[255u8; 16]);
+        for item_result in table.range(start_key..=end_key)?.take(RELATED_CANDIDATES_PER_TERM) {
+            let (key, _value) = item_result?;
+            let (_, timestamp, candidate_id) = key.value();
+            if *candidate_id == post_id_bytes {
+                continue;
+            }
+            let entry = scores.entry(*candidate_id).or_insert((0, timestamp));
+            entry.0 += weight;
+        }
+    }
+
+    let mut ranked: Vec<([u8; 16], (u32, i64))> = scores.into_iter().collect();
+    // Highest score first; the smaller negated timestamp is the newer post.
+    ranked.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then(a.1 .1.cmp(&b.1 .1)));
+
+    let posts = ranked
+        .into_iter()
+        .take(limit as usize)
+        .filter_map(|(candidate_id, _)| {
+            let guard = metadata_table.get(&candidate_id).ok().flatten()?;
+            let metadata = serde_json::from_str(guard.value()).ok()?;
+            Some(PostSummary { id: Uuid::from_bytes(candidate_id).to_string(), metadata, author: None })
+        })
+        .collect();
+    Ok(Some(posts))
+}
+
+/// Reads the summaries of several published posts in one transaction, keeping the given order.
+/// IDs without metadata (e.g. deleted meanwhile) are skipped.
+pub fn read_post_summaries_by_ids(db: &Database, ids: &[[u8; 16]]) -> Result<Vec<PostSummary>, DbError> {
+    let read_txn = db.begin_read()?;
+    let metadata_table = read_txn.open_table(METADATA)?;
+
+    let mut posts = Vec::with_capacity(ids.len());
+    for post_id_bytes in ids {
+        if let Some(guard) = metadata_table.get(post_id_bytes)? {
+            if let Ok(metadata) = serde_json::from_str::<PostMetadata>(guard.value()) {
+                posts.push(PostSummary { id: Uuid::from_bytes(*post_id_bytes).to_string(), metadata, author: None });
+            }
+        }
+    }
+    Ok(posts)
+}
+
 // This remains a table scan, but is acceptable for a specific backend search feature.
+// Returns the requested page together with the total number of matches.
 pub fn read_post_summaries_by_title(
     db: &Database,
     title_query: &str,
     limit: u32,
     offset: u32,
-) -> Result<Vec<PostSummary>, DbError> {
+) -> Result<(Vec<PostSummary>, u64), DbError> {
     let read_txn = db.begin_read()?;
     let metadata_table = read_txn.open_table(METADATA)?;
     
@@ -776,7 +1608,7 @@ pub fn read_post_summaries_by_title(
                 .ok()
                 .and_then(|metadata| {
                     if metadata.title.to_lowercase().contains(&lower_title_query) {
-                        Some(PostSummary { id: post_uuid.to_string(), metadata })
+                        Some(PostSummary { id: post_uuid.to_string(), metadata, author: None })
                     } else {
                         None
                     }
@@ -784,6 +1616,7 @@ pub fn read_post_summaries_by_title(
         }).collect();
 
     posts.sort_by(|a, b| b.metadata.created_at.cmp(&a.metadata.created_at));
+    let total = posts.len() as u64;
 
     let paginated_posts = posts
         .into_iter()
@@ -791,7 +1624,7 @@ pub fn read_post_summaries_by_title(
         .take(limit as usize)
         .collect();
 
-    Ok(paginated_posts)
+    Ok((paginated_posts, total))
 }
 
 pub fn read_post_summaries_by_keyword(
@@ -820,6 +1653,7 @@ pub fn read_post_summaries_by_keyword(
                     serde_json::from_str(meta_str.value()).ok().map(|metadata| PostSummary {
                         id: post_uuid.to_string(),
                         metadata,
+                        author: None,
                     })
                 })
             })
@@ -827,93 +1661,3 @@ pub fn read_post_summaries_by_keyword(
         .collect();
     Ok(posts)
 }
-
-fn get_post_ids_for_tag(
-    db: &Database,
-    tag: &str,
-) -> Result<HashSet<[u8; 16]>, DbError> {
-    let read_txn = db.begin_read()?;
-    let tag_index = read_txn.open_table(TAG_INDEX)?;
-
-    let lower_tag = tag.to_lowercase();
-    let start_key = (lower_tag.as_str(), i64::MIN, &[0u8; 16]);
-    let end_key = (lower_tag.as_str(), i64::MAX, &[255u8; 16]);
-
-    let mut ids = HashSet::new();
-    for item_result in tag_index.range(start_key..=end_key)? {
-        let (key, _) = item_result?;
-        // The post ID is the third element in the composite key
-        ids.insert(*key.value().2);
-    }
-    Ok(ids)
-}
-
-
-// --- Function 2: NEW PUBLIC FUNCTION ---
-/// Reads post summaries that contain ALL of the specified tags (intersection).
-/// This is the main function that performs the filtering logic.
-pub fn read_post_summaries_by_tags_intersection(
-    db: &Database,
-    tags: &[String],
-    limit: u32,
-    offset: u32,
-) -> Result<Vec<PostSummary>, DbError> {
-    // Safety Check: If for some reason this is called with no tags,
-    // return an empty list immediately.
-    if tags.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    // Start with the set of post IDs from the first tag.
-    let mut intersecting_ids: HashSet<[u8; 16]> = get_post_ids_for_tag(db, &tags[0])?;
-
-    // If there are more tags, iterate through them and shrink the ID set.
-    if tags.len() > 1 {
-        for tag in &tags[1..] {
-            // Early Exit: If the set of matching IDs is already empty,
-            // there's no need to check further.
-            if intersecting_ids.is_empty() {
-                break;
-            }
-            let next_tag_ids = get_post_ids_for_tag(db, tag)?;
-            // Keep only the IDs that are also in the next tag's set.
-            intersecting_ids.retain(|id| next_tag_ids.contains(id));
-        }
-    }
-    
-    // If no posts matched all tags, return early.
-    if intersecting_ids.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    // Now, fetch the full metadata for the final intersecting post IDs.
-    let read_txn = db.begin_read()?;
-    let metadata_table = read_txn.open_table(METADATA)?;
-
-    let mut summaries: Vec<PostSummary> = intersecting_ids
-        .into_iter()
-        .filter_map(|id_bytes| {
-            metadata_table.get(&id_bytes).ok().flatten().and_then(|meta_str_guard| {
-                let post_uuid = Uuid::from_bytes(id_bytes);
-                serde_json::from_str::<PostMetadata>(meta_str_guard.value())
-                    .ok()
-                    .map(|metadata| PostSummary {
-                        id: post_uuid.to_string(),
-                        metadata,
-                    })
-            })
-        })
-        .collect();
-
-    // IMPORTANT: Sort the full list of results by date DESCENDING before applying pagination.
-    summaries.sort_by(|a, b| b.metadata.created_at.cmp(&a.metadata.created_at));
-
-    // Apply pagination (limit and offset) at the very end.
-    let paginated_summaries = summaries
-        .into_iter()
-        .skip(offset as usize)
-        .take(limit as usize)
-        .collect();
-
-    Ok(paginated_summaries)
-}
\ No newline at end of file
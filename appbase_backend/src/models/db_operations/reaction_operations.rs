@@ -0,0 +1,97 @@
+use redb::{Database, ReadableTable, TableDefinition, WriteTransaction};
+
+use super::posts_db_operations::{DbError, METADATA};
+
+// --- Anonymous reactions to PUBLISHED posts ---
+// (post id, kind) -> number of reactions.
+pub const REACTION_COUNTS: TableDefinition<(&[u8; 16], &str), u64> = TableDefinition::new("reaction_counts");
+// (token period, token nonce, post id, kind) -> (). One row per counted reaction, so a
+// token can react once per post and kind. Rows of expired periods are pruned.
+pub const REACTION_VOTES: TableDefinition<VoteKey, ()> = TableDefinition::new("reaction_votes");
+
+type VoteKey = (i64, &'static [u8; 16], &'static [u8; 16], &'static str);
+type OwnedVoteKey = (i64, [u8; 16], [u8; 16], String);
+
+const MIN_ID: [u8; 16] = [0u8; 16];
+
+/// Counts a reaction unless this token already gave it. Returns whether it was counted.
+/// Votes of periods before `oldest_valid_period` are dropped in the same transaction.
+///
+/// Like view counters, reactions are not published content: no publish-state bump.
+pub fn add_reaction(
+    db: &Database,
+    period: i64,
+    oldest_valid_period: i64,
+    nonce: &[u8; 16],
+    post_id_bytes: &[u8; 16],
+    kind: &str,
+) -> Result<bool, DbError> {
+    let write_txn = db.begin_write()?;
+    let counted;
+    {
+        if write_txn.open_table(METADATA)?.get(post_id_bytes)?.is_none() {
+            return Err(DbError::NotFound("Post not found".to_string()));
+        }
+        let mut votes = write_txn.open_table(REACTION_VOTES)?;
+        let mut counts = write_txn.open_table(REACTION_COUNTS)?;
+
+        let expired: Vec<OwnedVoteKey> = {
+            let mut expired = Vec::new();
+            for item_result in votes.range((i64::MIN, &MIN_ID, &MIN_ID, "")..(oldest_valid_period, &MIN_ID, &MIN_ID, ""))? {
+                let (key, _) = item_result?;
+                let (p, n, post, k) = key.value();
+                expired.push((p, *n, *post, k.to_string()));
+            }
+            expired
+        };
+        for (p, n, post, k) in &expired {
+            votes.remove((*p, n, post, k.as_str()))?;
+        }
+
+        counted = votes.insert((period, nonce, post_id_bytes, kind), ())?.is_none();
+        if counted {
+            let current = counts.get((post_id_bytes, kind))?.map(|g| g.value()).unwrap_or(0);
+            counts.insert((post_id_bytes, kind), current + 1)?;
+        }
+    }
+    write_txn.commit()?;
+    Ok(counted)
+}
+
+/// Every `(kind, count)` recorded for a post, by kind.
+pub fn read_reaction_counts(db: &Database, post_id_bytes: &[u8; 16]) -> Result<Vec<(String, u64)>, DbError> {
+    let read_txn = db.begin_read()?;
+    let counts = read_txn.open_table(REACTION_COUNTS)?;
+    let mut reactions = Vec::new();
+    for item_result in counts.range((post_id_bytes, "")..)? {
+        let (key, count) = item_result?;
+        let (post, kind) = key.value();
+        if post != post_id_bytes {
+            break;
+        }
+        reactions.push((kind.to_string(), count.value()));
+    }
+    Ok(reactions)
+}
+
+/// Removes a post's reaction counts inside the caller's write transaction.
+/// Its votes expire with their token period.
+pub fn remove_post_reactions(txn: &WriteTransaction, post_id_bytes: &[u8; 16]) -> Result<(), DbError> {
+    let mut counts = txn.open_table(REACTION_COUNTS)?;
+    let kinds: Vec<String> = {
+        let mut kinds = Vec::new();
+        for item_result in counts.range((post_id_bytes, "")..)? {
+            let (key, _) = item_result?;
+            let (post, kind) = key.value();
+            if post != post_id_bytes {
+                break;
+            }
+            kinds.push(kind.to_string());
+        }
+        kinds
+    };
+    for kind in kinds {
+        counts.remove((post_id_bytes, kind.as_str()))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use lru::LruCache;
+use serde::Serialize;
+use std::any::Any;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::config::ReadCacheConfig;
+
+/// What a cached value was read from. Single-post entries are dropped only when
+/// that post changes; `Query` entries (lists, counts, tag data) span many posts
+/// and are dropped on any change to the published data.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CacheKey {
+    Post([u8; 16]),
+    PostSummary([u8; 16]),
+    Query(String),
+}
+
+/// Counters shown on the admin dashboard.
+#[derive(Debug, Serialize)]
+pub struct ReadCacheStats {
+    pub enabled: bool,
+    pub capacity: usize,
+    pub ttl_seconds: u64,
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub invalidations: u64,
+    pub hit_rate_percent: f64,
+}
+
+struct Entry {
+    value: Arc<dyn Any + Send + Sync>,
+    stored_at: Instant,
+}
+
+struct Inner {
+    entries: LruCache<CacheKey, Entry>,
+    // Bumped on every invalidation. A value loaded while a write was committing is
+    // only stored if no invalidation happened in between, so it can't resurrect stale data.
+    generation: u64,
+}
+
+struct ReadCache {
+    inner: Option<Mutex<Inner>>,
+    capacity: usize,
+    ttl: Duration,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl ReadCache {
+    fn new(config: &ReadCacheConfig) -> Self {
+        let capacity = if config.enabled { config.max_entries } else { 0 };
+        ReadCache {
+            inner: NonZeroUsize::new(capacity).map(|size| Mutex::new(Inner { entries: LruCache::new(size), generation: 0 })),
+            capacity,
+            ttl: Duration::from_secs(config.ttl_seconds),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+        }
+    }
+
+    fn lock(&self) -> Option<MutexGuard<'_, Inner>> {
+        self.inner.as_ref().map(|inner| {
+            inner.lock().unwrap_or_else(|poisoned| {
+                log::error!("Mutex for the read cache was poisoned! Using stale data.");
+                poisoned.into_inner()
+            })
+        })
+    }
+
+    fn invalidate(&self, predicate: impl Fn(&CacheKey) -> bool) {
+        if let Some(mut inner) = self.lock() {
+            inner.generation += 1;
+            let stale: Vec<CacheKey> = inner.entries.iter().map(|(k, _)| k).filter(|k| predicate(k)).cloned().collect();
+            for key in stale {
+                inner.entries.pop(&key);
+            }
+            self.invalidations.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+static READ_CACHE: OnceLock<ReadCache> = OnceLock::new();
+
+fn cache() -> &'static ReadCache {
+    READ_CACHE.get_or_init(|| ReadCache::new(&ReadCacheConfig::default()))
+}
+
+/// Sets the cache size and TTL. Must run before the first read; later calls are ignored.
+pub fn configure(config: &ReadCacheConfig) {
+    if READ_CACHE.set(ReadCache::new(config)).is_err() {
+        log::warn!("Read cache was already initialised; ignoring new configuration.");
+    }
+}
+
+/// Returns the cached value for `key`, or runs `load` and caches its result.
+/// Errors are never cached.
+pub fn cached<T, E>(key: CacheKey, load: impl FnOnce() -> Result<T, E>) -> Result<T, E>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let cache = cache();
+    let generation = match cache.lock() {
+        None => return load(),
+        Some(mut inner) => {
+            let fresh = inner
+                .entries
+                .get(&key)
+                .filter(|entry| entry.stored_at.elapsed() < cache.ttl)
+                .and_then(|entry| entry.value.downcast_ref::<T>().cloned());
+            if let Some(value) = fresh {
+                cache.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(value);
+            }
+            inner.generation
+        }
+    };
+
+    cache.misses.fetch_add(1, Ordering::Relaxed);
+    let value = load()?;
+    if let Some(mut inner) = cache.lock() {
+        if inner.generation == generation {
+            inner.entries.put(key, Entry { value: Arc::new(value.clone()), stored_at: Instant::now() });
+        }
+    }
+    Ok(value)
+}
+
+/// Drops everything derived from one post. Call after the write transaction commits.
+pub fn invalidate_post(post_id: &[u8; 16]) {
+    cache().invalidate(|key| match key {
+        CacheKey::Post(id) | CacheKey::PostSummary(id) => id == post_id,
+        CacheKey::Query(_) => true,
+    });
+}
+
+/// Drops list, count and tag entries but keeps single posts, e.g. after tag changes.
+pub fn invalidate_queries() {
+    cache().invalidate(|key| matches!(key, CacheKey::Query(_)));
+}
+
+/// Drops every entry, for writes that can touch arbitrary posts (advanced DB manager).
+pub fn invalidate_all() {
+    cache().invalidate(|_| true);
+}
+
+pub fn stats() -> ReadCacheStats {
+    let cache = cache();
+    let entries = cache.lock().map(|inner| inner.entries.len()).unwrap_or(0);
+    let hits = cache.hits.load(Ordering::Relaxed);
+    let misses = cache.misses.load(Ordering::Relaxed);
+    let lookups = hits + misses;
+    ReadCacheStats {
+        enabled: cache.inner.is_some(),
+        capacity: cache.capacity,
+        ttl_seconds: cache.ttl.as_secs(),
+        entries,
+        hits,
+        misses,
+        invalidations: cache.invalidations.load(Ordering::Relaxed),
+        hit_rate_percent: if lookups == 0 { 0.0 } else { (hits as f64 * 1000.0 / lookups as f64).round() / 10.0 },
+    }
+}
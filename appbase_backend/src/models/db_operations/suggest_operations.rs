@@ -0,0 +1,226 @@
+use redb::{Database, ReadableTable, TableDefinition, WriteTransaction};
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Bound;
+use uuid::Uuid;
+
+use super::posts_db_operations::{DbError, AVAILABLE_TAGS, METADATA, SEARCH_APPEAR_KEYWORD_INDEX, TAG_INDEX};
+use super::read_cache::{self, CacheKey};
+use crate::models::{PostMetadata, Suggestions, TermSuggestion, TitleSuggestion};
+
+// (lowercase title word, negated creation timestamp, post id) -> (), for PUBLISHED posts.
+// Keyed like `TAG_INDEX` so a prefix is a single range scan.
+pub const TITLE_WORD_INDEX: TableDefinition<(&str, i64, &[u8; 16]), ()> = TableDefinition::new("title_word_index");
+
+// Upper bounds per group, so a one-letter prefix stays cheap: index entries read, distinct
+// terms looked at, and entries counted per term (counts of more popular terms saturate there).
+const MAX_SCANNED_ENTRIES: usize = 2_000;
+const MAX_SCANNED_TERMS: usize = 200;
+const MAX_COUNTED_POSTS: usize = 1_000;
+
+/// Which groups of completions to compute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SuggestKinds {
+    pub tags: bool,
+    pub keywords: bool,
+    pub titles: bool,
+    /// Tags of the admin-managed vocabulary only. Opt-in; used by the contributor editor.
+    pub vocabulary: bool,
+}
+
+impl Default for SuggestKinds {
+    fn default() -> Self {
+        SuggestKinds { tags: true, keywords: true, titles: true, vocabulary: false }
+    }
+}
+
+/// The distinct lowercase words of a title. Titles are stored entity-escaped, so they are
+/// decoded first: "Tom &amp; Jerry" yields `tom` and `jerry`, not `amp`.
+fn title_words(title: &str) -> HashSet<String> {
+    html_escape::decode_html_entities(title)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && word.len() <= 40)
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Adds a published post's title to the index inside the caller's write transaction.
+/// `timestamp` is the negated creation time, as in the other indices.
+/// Returns the words that were not indexed for this post before.
+pub fn index_title(txn: &WriteTransaction, post_id_bytes: &[u8; 16], title: &str, timestamp: i64) -> Result<Vec<String>, DbError> {
+    let mut index = txn.open_table(TITLE_WORD_INDEX)?;
+    let mut added = Vec::new();
+    for word in title_words(title) {
+        if index.insert((word.as_str(), timestamp, post_id_bytes), ())?.is_none() {
+            added.push(word);
+        }
+    }
+    Ok(added)
+}
+
+/// Removes the entries written by `index_title` for the same title and timestamp.
+/// Returns the words that were actually removed.
+pub fn remove_title(txn: &WriteTransaction, post_id_bytes: &[u8; 16], title: &str, timestamp: i64) -> Result<Vec<String>, DbError> {
+    let mut index = txn.open_table(TITLE_WORD_INDEX)?;
+    let mut removed = Vec::new();
+    for word in title_words(title) {
+        if index.remove((word.as_str(), timestamp, post_id_bytes))?.is_some() {
+            removed.push(word);
+        }
+    }
+    Ok(removed)
+}
+
+/// Counts posts per term for the terms in a `(term, timestamp, id)` index that start with
+/// `prefix`. Each term is counted on its own and the scan then jumps past it, so one very
+/// popular term cannot hide the others.
+fn scan_term_prefix(
+    index: &redb::ReadOnlyTable<(&str, i64, &[u8; 16]), ()>,
+    prefix: &str,
+    counts: &mut BTreeMap<String, u64>,
+) -> Result<(), DbError> {
+    let mut previous: Option<String> = None;
+    for _ in 0..MAX_SCANNED_TERMS {
+        let lower = match &previous {
+            Some(term) => Bound::Excluded((term.as_str(), i64::MAX, &[255u8; 16])),
+            None => Bound::Included((prefix, i64::MIN, &[0u8; 16])),
+        };
+        let term = match index.range::<(&str, i64, &[u8; 16])>((lower, Bound::Unbounded))?.next() {
+            Some(item_result) => item_result?.0.value().0.to_string(),
+            None => break,
+        };
+        if !term.starts_with(prefix) {
+            break;
+        }
+
+        let term_range = (term.as_str(), i64::MIN, &[0u8; 16])..=(term.as_str(), i64::MAX, &[255u8; 16]);
+        let post_count = index.range(term_range)?.take(MAX_COUNTED_POSTS).count();
+        counts.insert(term.clone(), post_count as u64);
+        previous = Some(term);
+    }
+    Ok(())
+}
+
+/// Most-used terms first, then alphabetical.
+fn rank_terms(counts: BTreeMap<String, u64>, limit: usize) -> Vec<TermSuggestion> {
+    let mut terms: Vec<TermSuggestion> =
+        counts.into_iter().map(|(value, post_count)| TermSuggestion { value, post_count }).collect();
+    terms.sort_by(|a, b| b.post_count.cmp(&a.post_count).then_with(|| a.value.cmp(&b.value)));
+    terms.truncate(limit);
+    terms
+}
+
+/// Prefix completions for `query` (already trimmed and lowercased): tags from the vocabulary
+/// and `TAG_INDEX`, vocabulary tags alone, search keywords, and titles of published posts,
+/// newest first.
+/// Every group is read with range scans; at most `limit` entries are returned per group.
+pub fn suggest(db: &Database, query: &str, kinds: SuggestKinds, limit: u32) -> Result<Suggestions, DbError> {
+    let key = CacheKey::Query(format!("suggest{:?}", (query, kinds, limit)));
+    read_cache::cached(key, || load_suggest(db, query, kinds, limit))
+}
+
+fn load_suggest(db: &Database, query: &str, kinds: SuggestKinds, limit: u32) -> Result<Suggestions, DbError> {
+    let limit = limit as usize;
+    let read_txn = db.begin_read()?;
+    let mut suggestions = Suggestions::default();
+
+    if kinds.tags || kinds.vocabulary {
+        let mut counts = BTreeMap::new();
+        scan_term_prefix(&read_txn.open_table(TAG_INDEX)?, query, &mut counts)?;
+
+        // Vocabulary tags nobody has used yet are still valid completions.
+        let mut vocabulary = BTreeMap::new();
+        let available = read_txn.open_table(AVAILABLE_TAGS)?;
+        for item_result in available.range(query..)?.take(MAX_SCANNED_ENTRIES) {
+            let (tag, _) = item_result?;
+            let tag = tag.value();
+            if !tag.starts_with(query) {
+                break;
+            }
+            vocabulary.insert(tag.to_string(), counts.get(tag).copied().unwrap_or(0));
+        }
+
+        if kinds.vocabulary {
+            suggestions.vocabulary = rank_terms(vocabulary.clone(), limit);
+        }
+        if kinds.tags {
+            // `TAG_INDEX` also holds generated path ancestors and tags since removed from the vocabulary.
+            counts.extend(vocabulary);
+            suggestions.tags = rank_terms(counts, limit);
+        }
+    }
+
+    if kinds.keywords {
+        let mut counts = BTreeMap::new();
+        scan_term_prefix(&read_txn.open_table(SEARCH_APPEAR_KEYWORD_INDEX)?, query, &mut counts)?;
+        suggestions.keywords = rank_terms(counts, limit);
+    }
+
+    if kinds.titles {
+        // The word being typed is the last one; earlier words must appear in the title as typed.
+        let last_word = query.split(|c: char| !c.is_alphanumeric()).rfind(|w| !w.is_empty()).unwrap_or("");
+        if !last_word.is_empty() {
+            let index = read_txn.open_table(TITLE_WORD_INDEX)?;
+            let mut candidates: Vec<(i64, [u8; 16])> = Vec::new();
+            let start_key = (last_word, i64::MIN, &[0u8; 16]);
+            for item_result in index.range(start_key..)?.take(MAX_SCANNED_ENTRIES) {
+                let (key, _) = item_result?;
+                let (word, timestamp, post_id) = key.value();
+                if !word.starts_with(last_word) {
+                    break;
+                }
+                candidates.push((timestamp, *post_id));
+            }
+            // Negated timestamps: ascending order is newest first.
+            candidates.sort_unstable();
+            candidates.dedup();
+
+            let metadata_table = read_txn.open_table(METADATA)?;
+            for (_, post_id) in candidates {
+                if suggestions.titles.len() >= limit {
+                    break;
+                }
+                let Some(guard) = metadata_table.get(&post_id)? else { continue };
+                let metadata: PostMetadata = serde_json::from_str(guard.value())?;
+                if !html_escape::decode_html_entities(&metadata.title).to_lowercase().contains(query) {
+                    continue;
+                }
+                suggestions.titles.push(TitleSuggestion {
+                    id: Uuid::from_bytes(post_id).to_string(),
+                    title: metadata.title,
+                    slug: metadata.slug,
+                });
+            }
+        }
+    }
+
+    Ok(suggestions)
+}
+
+/// Drops and rebuilds the title index from the published `METADATA` table.
+/// Used by the setup CLI for databases that predate the index.
+pub fn rebuild_title_index(db: &Database) -> Result<u64, DbError> {
+    let write_txn = db.begin_write()?;
+    let mut indexed = 0u64;
+    {
+        write_txn.delete_table(TITLE_WORD_INDEX)?;
+
+        let posts: Vec<([u8; 16], PostMetadata)> = {
+            let metadata_table = write_txn.open_table(METADATA)?;
+            let mut posts = Vec::new();
+            for item_result in metadata_table.iter()? {
+                let (id_guard, meta_guard) = item_result?;
+                if let Ok(metadata) = serde_json::from_str(meta_guard.value()) {
+                    posts.push((*id_guard.value(), metadata));
+                }
+            }
+            posts
+        };
+
+        for (post_id, metadata) in &posts {
+            index_title(&write_txn, post_id, &metadata.title, -metadata.created_at.timestamp())?;
+            indexed += 1;
+        }
+    }
+    write_txn.commit()?;
+    Ok(indexed)
+}
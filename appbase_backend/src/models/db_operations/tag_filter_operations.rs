@@ -0,0 +1,332 @@
+use redb::{Database, ReadOnlyTable, ReadableTable};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::posts_db_operations::{DbError, CHRONOLOGICAL_INDEX, METADATA, TAG_INDEX};
+use crate::models::{PostMetadata, PostSummary};
+
+// Guards against pathological expressions from the public API.
+const MAX_EXPRESSION_LENGTH: usize = 512;
+pub const MAX_TAG_TERMS: usize = 32;
+const MAX_NESTING_DEPTH: usize = 16;
+
+/// A boolean query over tags, e.g. `rust AND (actix OR axum) NOT draft`.
+#[derive(Debug, Clone)]
+pub enum TagExpr {
+    Tag(String),
+    And(Box<TagExpr>, Box<TagExpr>),
+    Or(Box<TagExpr>, Box<TagExpr>),
+    Not(Box<TagExpr>),
+}
+
+impl TagExpr {
+    /// Builds `all1 AND all2 AND (any1 OR any2) AND NOT ex1 AND NOT ex2`.
+    /// Returns `None` when all three lists are empty.
+    pub fn from_sets(all: &[String], any: &[String], exclude: &[String]) -> Option<TagExpr> {
+        let mut parts: Vec<TagExpr> = all.iter().map(|t| TagExpr::Tag(t.to_lowercase())).collect();
+
+        let any_expr = any
+            .iter()
+            .map(|t| TagExpr::Tag(t.to_lowercase()))
+            .reduce(|a, b| TagExpr::Or(Box::new(a), Box::new(b)));
+        parts.extend(any_expr);
+
+        parts.extend(exclude.iter().map(|t| TagExpr::Not(Box::new(TagExpr::Tag(t.to_lowercase())))));
+
+        parts.into_iter().reduce(|a, b| TagExpr::And(Box::new(a), Box::new(b)))
+    }
+
+    fn term_count(&self) -> usize {
+        match self {
+            TagExpr::Tag(_) => 1,
+            TagExpr::And(a, b) | TagExpr::Or(a, b) => a.term_count() + b.term_count(),
+            TagExpr::Not(a) => a.term_count(),
+        }
+    }
+
+    /// Rejects expressions with more than `MAX_TAG_TERMS` tags; each one costs a `TAG_INDEX` scan.
+    pub fn check_term_limit(&self) -> Result<(), String> {
+        if self.term_count() > MAX_TAG_TERMS {
+            return Err(format!("Expression uses more than {} tags.", MAX_TAG_TERMS));
+        }
+        Ok(())
+    }
+}
+
+// ====================================================================
+// ============================ PARSER ================================
+// ====================================================================
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+    Tag(String),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' {
+            chars.next();
+            tokens.push(Token::Open);
+        } else if c == ')' {
+            chars.next();
+            tokens.push(Token::Close);
+        } else if c == '"' {
+            chars.next();
+            let mut tag = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some(ch) => tag.push(ch),
+                    None => return Err("Unterminated quoted tag.".to_string()),
+                }
+            }
+            let tag = tag.trim().to_lowercase();
+            if tag.is_empty() {
+                return Err("Empty quoted tag.".to_string());
+            }
+            tokens.push(Token::Tag(tag));
+        } else {
+            let mut word = String::new();
+            while let Some(&ch) = chars.peek() {
+                if ch.is_whitespace() || ch == '(' || ch == ')' || ch == '"' {
+                    break;
+                }
+                word.push(ch);
+                chars.next();
+            }
+            tokens.push(match word.to_uppercase().as_str() {
+                "AND" => Token::And,
+                "OR" => Token::Or,
+                "NOT" => Token::Not,
+                _ => Token::Tag(word.to_lowercase()),
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    // or_expr := and_expr ("OR" and_expr)*
+    fn parse_or(&mut self) -> Result<TagExpr, String> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            let right = self.parse_and()?;
+            left = TagExpr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    // and_expr := unary (("AND" unary) | ("NOT" unary))*   -- "a NOT b" means "a AND NOT b"
+    fn parse_and(&mut self) -> Result<TagExpr, String> {
+        let mut left = self.parse_unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.next();
+                    let right = self.parse_unary()?;
+                    left = TagExpr::And(Box::new(left), Box::new(right));
+                }
+                Some(Token::Not) => {
+                    self.next();
+                    let right = self.parse_unary()?;
+                    left = TagExpr::And(Box::new(left), Box::new(TagExpr::Not(Box::new(right))));
+                }
+                _ => return Ok(left),
+            }
+        }
+    }
+
+    // unary := "NOT" unary | primary
+    fn parse_unary(&mut self) -> Result<TagExpr, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            let inner = self.parse_unary()?;
+            return Ok(TagExpr::Not(Box::new(inner)));
+        }
+        self.parse_primary()
+    }
+
+    // primary := "(" or_expr ")" | TAG
+    fn parse_primary(&mut self) -> Result<TagExpr, String> {
+        match self.next() {
+            Some(Token::Tag(tag)) => Ok(TagExpr::Tag(tag.clone())),
+            Some(Token::Open) => {
+                self.depth += 1;
+                if self.depth > MAX_NESTING_DEPTH {
+                    return Err("Expression is nested too deeply.".to_string());
+                }
+                let inner = self.parse_or()?;
+                if self.next() != Some(&Token::Close) {
+                    return Err("Missing closing parenthesis.".to_string());
+                }
+                self.depth -= 1;
+                Ok(inner)
+            }
+            Some(other) => Err(format!("Unexpected {:?} in expression.", other)),
+            None => Err("Unexpected end of expression.".to_string()),
+        }
+    }
+}
+
+/// Parses a tag expression such as `rust AND (actix OR axum) NOT draft`.
+/// Operators are case-insensitive; tags containing spaces can be double-quoted.
+pub fn parse_tag_expression(input: &str) -> Result<TagExpr, String> {
+    if input.len() > MAX_EXPRESSION_LENGTH {
+        return Err(format!("Expression is longer than {} characters.", MAX_EXPRESSION_LENGTH));
+    }
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Err("Expression is empty.".to_string());
+    }
+
+    let mut parser = Parser { tokens, pos: 0, depth: 0 };
+    let expr = parser.parse_or()?;
+    if let Some(token) = parser.peek() {
+        return Err(format!("Unexpected {:?} in expression.", token));
+    }
+    expr.check_term_limit()?;
+    Ok(expr)
+}
+
+// ====================================================================
+// =========================== EVALUATION =============================
+// ====================================================================
+
+/// Post id -> negated creation timestamp (the sort key of the indices).
+type PostSet = HashMap<[u8; 16], i64>;
+type TagIndexTable<'txn> = ReadOnlyTable<'txn, (&'static str, i64, &'static [u8; 16]), ()>;
+
+/// An evaluated sub-expression. `negated` means "every published post except these",
+/// which lets `a NOT b` be computed as a set difference without loading all posts.
+struct EvalSet {
+    posts: PostSet,
+    negated: bool,
+}
+
+fn intersect(a: PostSet, b: &PostSet) -> PostSet {
+    a.into_iter().filter(|(id, _)| b.contains_key(id)).collect()
+}
+
+fn difference(a: PostSet, b: &PostSet) -> PostSet {
+    a.into_iter().filter(|(id, _)| !b.contains_key(id)).collect()
+}
+
+fn union(mut a: PostSet, b: PostSet) -> PostSet {
+    a.extend(b);
+    a
+}
+
+fn tag_posts(tag_index: &TagIndexTable, tag: &str) -> Result<PostSet, DbError> {
+    let start_key = (tag, i64::MIN, &[0u8; 16]);
+    let end_key = (tag, i64::MAX, &[255u8; 16]);
+    let mut posts = PostSet::new();
+    for item_result in tag_index.range(start_key..=end_key)? {
+        let (key, _value) = item_result?;
+        let (_, timestamp, post_id) = key.value();
+        posts.insert(*post_id, timestamp);
+    }
+    Ok(posts)
+}
+
+fn evaluate(expr: &TagExpr, tag_index: &TagIndexTable) -> Result<EvalSet, DbError> {
+    Ok(match expr {
+        TagExpr::Tag(tag) => EvalSet { posts: tag_posts(tag_index, tag)?, negated: false },
+        TagExpr::Not(inner) => {
+            let inner = evaluate(inner, tag_index)?;
+            EvalSet { posts: inner.posts, negated: !inner.negated }
+        }
+        TagExpr::And(a, b) => {
+            let (a, b) = (evaluate(a, tag_index)?, evaluate(b, tag_index)?);
+            match (a.negated, b.negated) {
+                (false, false) => EvalSet { posts: intersect(a.posts, &b.posts), negated: false },
+                (false, true) => EvalSet { posts: difference(a.posts, &b.posts), negated: false },
+                (true, false) => EvalSet { posts: difference(b.posts, &a.posts), negated: false },
+                // NOT a AND NOT b == NOT (a OR b)
+                (true, true) => EvalSet { posts: union(a.posts, b.posts), negated: true },
+            }
+        }
+        TagExpr::Or(a, b) => {
+            let (a, b) = (evaluate(a, tag_index)?, evaluate(b, tag_index)?);
+            match (a.negated, b.negated) {
+                (false, false) => EvalSet { posts: union(a.posts, b.posts), negated: false },
+                // a OR NOT b == NOT (b - a)
+                (false, true) => EvalSet { posts: difference(b.posts, &a.posts), negated: true },
+                (true, false) => EvalSet { posts: difference(a.posts, &b.posts), negated: true },
+                // NOT a OR NOT b == NOT (a AND b)
+                (true, true) => EvalSet { posts: intersect(a.posts, &b.posts), negated: true },
+            }
+        }
+    })
+}
+
+/// Evaluates a tag expression as set operations over `TAG_INDEX` ranges and returns one
+/// page of matching published posts, newest first, together with the total match count.
+/// Metadata is only loaded for the posts on the requested page.
+pub fn read_post_summaries_by_tag_expression(
+    db: &Database,
+    expr: &TagExpr,
+    limit: u32,
+    offset: u32,
+) -> Result<(Vec<PostSummary>, u64), DbError> {
+    let read_txn = db.begin_read()?;
+    let tag_index = read_txn.open_table(TAG_INDEX)?;
+    let metadata_table = read_txn.open_table(METADATA)?;
+
+    let result = evaluate(expr, &tag_index)?;
+    let mut matches: Vec<(i64, [u8; 16])> = if result.negated {
+        // Only needed for queries that are purely negative, e.g. "NOT draft".
+        let chrono_index = read_txn.open_table(CHRONOLOGICAL_INDEX)?;
+        let mut all = Vec::new();
+        for item_result in chrono_index.iter()? {
+            let (key, _value) = item_result?;
+            let (timestamp, post_id) = key.value();
+            if !result.posts.contains_key(post_id) {
+                all.push((timestamp, *post_id));
+            }
+        }
+        all
+    } else {
+        result.posts.into_iter().map(|(id, timestamp)| (timestamp, id)).collect()
+    };
+
+    // Negated timestamps: ascending order is newest first, same as the indices.
+    matches.sort_unstable();
+    let total = matches.len() as u64;
+
+    let mut posts = Vec::new();
+    for (_timestamp, post_id) in matches.into_iter().skip(offset as usize).take(limit as usize) {
+        if let Some(guard) = metadata_table.get(&post_id)? {
+            if let Ok(metadata) = serde_json::from_str::<PostMetadata>(guard.value()) {
+                posts.push(PostSummary { id: Uuid::from_bytes(post_id).to_string(), metadata, author: None });
+            }
+        }
+    }
+    Ok((posts, total))
+}
@@ -1,12 +1,13 @@
 
 
-use crate::models::{Contributor, PostAction}; // UPDATED
+use crate::models::{AuthorInfo, AuthorProfile, Contributor, ContributorProfile, PostAction, ProfileLink}; // UPDATED
 //use rusqlite::{params, Connection, OptionalExtension, Error as RusqliteError};
 use bcrypt::{hash, verify, BcryptError};
 use chrono::Utc;
 use crate::models::EditLogEntry;
 //use rusqlite::{Result as RusqliteResult};
-use rusqlite::{params, Connection, OptionalExtension, Error as RusqliteError, Result as RusqliteResult};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row, Error as RusqliteError, Result as RusqliteResult};
+use std::collections::HashMap;
 
 fn bcrypt_to_rusqlite_error(e: BcryptError) -> RusqliteError {
     RusqliteError::ToSqlConversionFailure(Box::new(e))
@@ -96,6 +97,7 @@ pub fn update_user(
 }
 
 pub fn delete_user(conn: &Connection, user_id: i32) -> Result<usize, RusqliteError> {
+    conn.execute("DELETE FROM contributor_profiles WHERE user_id = ?1", [user_id])?;
     conn.execute("DELETE FROM users WHERE id = ?1", [user_id])
 }
 
@@ -213,6 +215,14 @@ pub fn list_media_ids_for_user(conn: &Connection, user_id: i32) -> Result<Vec<St
     Ok(ids)
 }
 
+pub fn count_media_by_tag(conn: &Connection, tag_query: &str) -> Result<u64, RusqliteError> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM media_attachments WHERE tags LIKE ?1",
+        [format!("%{}%", tag_query)],
+        |row| row.get(0),
+    )
+}
+
 pub fn search_media_by_tag_from_db(
     conn: &Connection,
     tag_query: &str,
@@ -240,6 +250,22 @@ pub fn add_pending_post_ownership(conn: &Connection, post_id: &str, user_id: i32
     Ok(())
 }
 
+pub fn count_posts_by_user(conn: &Connection, user_id: i32) -> Result<u64, RusqliteError> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM post_ownership WHERE user_id = ?1",
+        [user_id],
+        |row| row.get(0),
+    )
+}
+
+pub fn count_pending_posts_by_user(conn: &Connection, user_id: i32) -> Result<u64, RusqliteError> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM pending_post_ownership WHERE user_id = ?1",
+        [user_id],
+        |row| row.get(0),
+    )
+}
+
 pub fn delete_pending_post_ownership(conn: &Connection, post_id: &str) -> Result<usize, RusqliteError> {
     conn.execute("DELETE FROM pending_post_ownership WHERE post_id = ?1", [post_id])
 }
@@ -305,4 +331,125 @@ pub fn append_to_edit_log(conn: &Connection, post_id: &str, editor_username: &st
         params![new_log_json, post_id],
     )?;
     Ok(())
-}
\ No newline at end of file
+}
+
+
+// --- Functions for contributor profiles ---
+fn parse_profile_links(links_json: Option<String>) -> Vec<ProfileLink> {
+    links_json
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn read_contributor_profile(conn: &Connection, user_id: i32) -> RusqliteResult<Option<ContributorProfile>> {
+    conn.query_row(
+        "SELECT display_name, bio, avatar_media_id, links FROM contributor_profiles WHERE user_id = ?1",
+        [user_id],
+        |row| {
+            Ok(ContributorProfile {
+                display_name: row.get(0)?,
+                bio: row.get(1)?,
+                avatar_media_id: row.get(2)?,
+                links: parse_profile_links(row.get(3)?),
+            })
+        },
+    )
+    .optional()
+}
+
+pub fn upsert_contributor_profile(conn: &Connection, user_id: i32, profile: &ContributorProfile) -> Result<(), RusqliteError> {
+    let links_json = serde_json::to_string(&profile.links).map_err(|e| RusqliteError::ToSqlConversionFailure(Box::new(e)))?;
+    conn.execute(
+        "INSERT INTO contributor_profiles (user_id, display_name, bio, avatar_media_id, links, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(user_id) DO UPDATE SET
+            display_name = excluded.display_name, bio = excluded.bio, avatar_media_id = excluded.avatar_media_id,
+            links = excluded.links, updated_at = excluded.updated_at",
+        params![user_id, profile.display_name, profile.bio, profile.avatar_media_id, links_json, Utc::now().to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+// Columns: username, display_name, bio, avatar_media_id, links, post_count, user id.
+// Public authors are the users who own at least one published post.
+const PUBLIC_AUTHOR_QUERY: &str = "SELECT u.username, p.display_name, p.bio, p.avatar_media_id, p.links, COUNT(o.post_id), u.id
+     FROM users u
+     JOIN post_ownership o ON o.user_id = u.id
+     LEFT JOIN contributor_profiles p ON p.user_id = u.id";
+
+fn row_to_author_profile(row: &Row) -> RusqliteResult<AuthorProfile> {
+    let username: String = row.get(0)?;
+    let display_name: Option<String> = row.get(1)?;
+    Ok(AuthorProfile {
+        display_name: display_name.filter(|n| !n.is_empty()).unwrap_or_else(|| username.clone()),
+        username,
+        bio: row.get(2)?,
+        avatar_media_id: row.get(3)?,
+        avatar_url: None,
+        links: parse_profile_links(row.get(4)?),
+        post_count: row.get(5)?,
+    })
+}
+
+pub fn count_public_authors(conn: &Connection) -> RusqliteResult<u64> {
+    conn.query_row("SELECT COUNT(DISTINCT user_id) FROM post_ownership", [], |row| row.get(0))
+}
+
+/// Reads a page of public authors ordered by display name. `avatar_url` is left for the caller to resolve.
+pub fn read_public_authors(conn: &Connection, limit: u32, offset: u32) -> RusqliteResult<Vec<AuthorProfile>> {
+    let sql = format!(
+        "{} GROUP BY u.id ORDER BY COALESCE(NULLIF(p.display_name, ''), u.username) COLLATE NOCASE LIMIT ?1 OFFSET ?2",
+        PUBLIC_AUTHOR_QUERY
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params![limit, offset], row_to_author_profile)?;
+    rows.collect()
+}
+
+/// Reads one public author together with their user id.
+pub fn read_public_author(conn: &Connection, username: &str) -> RusqliteResult<Option<(i32, AuthorProfile)>> {
+    let sql = format!("{} WHERE u.username = ?1 GROUP BY u.id", PUBLIC_AUTHOR_QUERY);
+    conn.query_row(&sql, [username], |row| Ok((row.get(6)?, row_to_author_profile(row)?)))
+        .optional()
+}
+
+/// Looks up the owner of each published post. Returns `post id -> (byline, avatar media id)`;
+/// posts without an owner are simply missing from the map.
+pub fn read_post_authors(
+    conn: &Connection,
+    post_ids: &[&str],
+) -> RusqliteResult<HashMap<String, (AuthorInfo, Option<String>)>> {
+    let mut authors = HashMap::new();
+    if post_ids.is_empty() {
+        return Ok(authors);
+    }
+
+    let placeholders = vec!["?"; post_ids.len()].join(", ");
+    let sql = format!(
+        "SELECT o.post_id, u.username, p.display_name, p.avatar_media_id
+         FROM post_ownership o
+         JOIN users u ON u.id = o.user_id
+         LEFT JOIN contributor_profiles p ON p.user_id = u.id
+         WHERE o.post_id IN ({})",
+        placeholders
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_from_iter(post_ids.iter()), |row| {
+        let username: String = row.get(1)?;
+        let display_name: Option<String> = row.get(2)?;
+        Ok((
+            row.get::<_, String>(0)?,
+            AuthorInfo {
+                display_name: display_name.filter(|n| !n.is_empty()).unwrap_or_else(|| username.clone()),
+                username,
+                avatar_url: None,
+            },
+            row.get::<_, Option<String>>(3)?,
+        ))
+    })?;
+    for row in rows {
+        let (post_id, info, avatar_media_id) = row?;
+        authors.insert(post_id, (info, avatar_media_id));
+    }
+    Ok(authors)
+}
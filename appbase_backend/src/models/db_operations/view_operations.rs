@@ -0,0 +1,95 @@
+use redb::{Database, ReadableTable, TableDefinition, WriteTransaction};
+use std::collections::HashMap;
+
+use super::posts_db_operations::{DbError, METADATA};
+
+// --- Daily view counters for PUBLISHED posts ---
+// Days are UTC day numbers (Unix timestamp / 86400). No visitor data is stored.
+// (post id, day) -> views. Per-post history for the contributor dashboard.
+pub const POST_DAILY_VIEWS: TableDefinition<(&[u8; 16], i64), u64> = TableDefinition::new("post_daily_views");
+// (day, post id) -> views. Same counts keyed by day, so a window is a single range scan.
+pub const DAILY_POST_VIEWS: TableDefinition<(i64, &[u8; 16]), u64> = TableDefinition::new("daily_post_views");
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// The UTC day number of a Unix timestamp.
+pub fn day_number(timestamp: i64) -> i64 {
+    timestamp.div_euclid(SECONDS_PER_DAY)
+}
+
+/// Adds buffered views, keyed by `(post id, day)`, in one write transaction.
+/// Views of posts that are no longer published are dropped. Returns the number of views stored.
+///
+/// Counters are not published content, so this neither bumps the publish state nor
+/// touches the read cache.
+pub fn record_views(db: &Database, views: &HashMap<([u8; 16], i64), u64>) -> Result<u64, DbError> {
+    if views.is_empty() {
+        return Ok(0);
+    }
+    let write_txn = db.begin_write()?;
+    let mut stored = 0u64;
+    {
+        let metadata_table = write_txn.open_table(METADATA)?;
+        let mut by_post = write_txn.open_table(POST_DAILY_VIEWS)?;
+        let mut by_day = write_txn.open_table(DAILY_POST_VIEWS)?;
+        for (&(post_id, day), &count) in views {
+            if count == 0 || metadata_table.get(&post_id)?.is_none() {
+                continue;
+            }
+            let current = by_post.get((&post_id, day))?.map(|g| g.value()).unwrap_or(0);
+            by_post.insert((&post_id, day), current + count)?;
+            by_day.insert((day, &post_id), current + count)?;
+            stored += count;
+        }
+    }
+    write_txn.commit()?;
+    Ok(stored)
+}
+
+/// Posts with the most views between `first_day` and `last_day` (inclusive), most viewed first.
+pub fn read_popular(db: &Database, first_day: i64, last_day: i64, limit: u32) -> Result<Vec<([u8; 16], u64)>, DbError> {
+    let read_txn = db.begin_read()?;
+    let by_day = read_txn.open_table(DAILY_POST_VIEWS)?;
+
+    let mut totals: HashMap<[u8; 16], u64> = HashMap::new();
+    for item_result in by_day.range((first_day, &[0u8; 16])..=(last_day, &[255u8; 16]))? {
+        let (key, views) = item_result?;
+        *totals.entry(*key.value().1).or_insert(0) += views.value();
+    }
+
+    let mut ranked: Vec<([u8; 16], u64)> = totals.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked.truncate(limit as usize);
+    Ok(ranked)
+}
+
+/// Every `(day, views)` recorded for a post, oldest first.
+pub fn read_post_daily_views(db: &Database, post_id_bytes: &[u8; 16]) -> Result<Vec<(i64, u64)>, DbError> {
+    let read_txn = db.begin_read()?;
+    let by_post = read_txn.open_table(POST_DAILY_VIEWS)?;
+    let mut days = Vec::new();
+    for item_result in by_post.range((post_id_bytes, i64::MIN)..=(post_id_bytes, i64::MAX))? {
+        let (key, views) = item_result?;
+        days.push((key.value().1, views.value()));
+    }
+    Ok(days)
+}
+
+/// Removes a post's counters inside the caller's write transaction.
+pub fn remove_post_views(txn: &WriteTransaction, post_id_bytes: &[u8; 16]) -> Result<(), DbError> {
+    let mut by_post = txn.open_table(POST_DAILY_VIEWS)?;
+    let mut by_day = txn.open_table(DAILY_POST_VIEWS)?;
+    let days: Vec<i64> = {
+        let mut days = Vec::new();
+        for item_result in by_post.range((post_id_bytes, i64::MIN)..=(post_id_bytes, i64::MAX))? {
+            let (key, _) = item_result?;
+            days.push(key.value().1);
+        }
+        days
+    };
+    for day in days {
+        by_post.remove((post_id_bytes, day))?;
+        by_day.remove((day, post_id_bytes))?;
+    }
+    Ok(())
+}
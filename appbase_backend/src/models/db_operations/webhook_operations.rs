@@ -0,0 +1,165 @@
+use crate::models::{Webhook, WebhookDelivery};
+use rusqlite::{params, Connection, OptionalExtension, Result as RusqliteResult, Row};
+
+/// A delivery that is due, with what is needed to send it.
+pub struct DueDelivery {
+    pub id: i64,
+    pub url: String,
+    pub secret: String,
+    pub event: String,
+    pub payload: String,
+    pub attempts: u32,
+}
+
+/// The result of one delivery attempt.
+pub struct AttemptOutcome<'a> {
+    pub attempted_at: &'a str,
+    pub response_status: Option<u16>,
+    pub error: Option<&'a str>,
+    /// When to try again; `None` ends the delivery as `delivered` or `failed`.
+    pub retry_at: Option<&'a str>,
+}
+
+fn split_events(events: &str) -> Vec<String> {
+    events.split(',').map(str::trim).filter(|e| !e.is_empty()).map(String::from).collect()
+}
+
+fn row_to_webhook(row: &Row) -> RusqliteResult<Webhook> {
+    let events: String = row.get(3)?;
+    Ok(Webhook {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        secret: row.get(2)?,
+        events: split_events(&events),
+        created_at: row.get(4)?,
+    })
+}
+
+pub fn create_webhook(conn: &Connection, url: &str, secret: &str, events: &[&str], created_at: &str) -> RusqliteResult<i64> {
+    conn.execute(
+        "INSERT INTO webhooks (url, secret, events, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![url, secret, events.join(","), created_at],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn read_webhooks(conn: &Connection) -> RusqliteResult<Vec<Webhook>> {
+    let mut stmt = conn.prepare("SELECT id, url, secret, events, created_at FROM webhooks ORDER BY id")?;
+    let rows = stmt.query_map([], row_to_webhook)?;
+    rows.collect()
+}
+
+/// Removes a webhook and its delivery log. Returns the number of webhooks removed.
+pub fn delete_webhook(conn: &Connection, webhook_id: i64) -> RusqliteResult<usize> {
+    conn.execute("DELETE FROM webhook_deliveries WHERE webhook_id = ?1", [webhook_id])?;
+    conn.execute("DELETE FROM webhooks WHERE id = ?1", [webhook_id])
+}
+
+/// Queues `payload` for every webhook subscribed to `event`. Returns the number queued.
+pub fn enqueue_event(conn: &Connection, event: &str, payload: &str, now: &str) -> RusqliteResult<usize> {
+    let subscribed: Vec<i64> = read_webhooks(conn)?
+        .into_iter()
+        .filter(|hook| hook.events.iter().any(|e| e == event))
+        .map(|hook| hook.id)
+        .collect();
+    for webhook_id in &subscribed {
+        conn.execute(
+            "INSERT INTO webhook_deliveries (webhook_id, event, payload, status, next_attempt_at, created_at)
+             VALUES (?1, ?2, ?3, 'pending', ?4, ?4)",
+            params![webhook_id, event, payload, now],
+        )?;
+    }
+    Ok(subscribed.len())
+}
+
+/// Pending deliveries whose next attempt is due at `now`, oldest first.
+pub fn read_due_deliveries(conn: &Connection, now: &str, limit: u32) -> RusqliteResult<Vec<DueDelivery>> {
+    let mut stmt = conn.prepare(
+        "SELECT d.id, w.url, w.secret, d.event, d.payload, d.attempts
+         FROM webhook_deliveries d JOIN webhooks w ON w.id = d.webhook_id
+         WHERE d.status = 'pending' AND d.next_attempt_at <= ?1
+         ORDER BY d.next_attempt_at, d.id LIMIT ?2",
+    )?;
+    let rows = stmt.query_map(params![now, limit], |row| {
+        Ok(DueDelivery {
+            id: row.get(0)?,
+            url: row.get(1)?,
+            secret: row.get(2)?,
+            event: row.get(3)?,
+            payload: row.get(4)?,
+            attempts: row.get(5)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Stores the outcome of an attempt and schedules the next one, if any.
+pub fn record_attempt(conn: &Connection, delivery_id: i64, outcome: &AttemptOutcome) -> RusqliteResult<()> {
+    let status = match (outcome.retry_at, outcome.error) {
+        (Some(_), _) => "pending",
+        (None, None) => "delivered",
+        (None, Some(_)) => "failed",
+    };
+    conn.execute(
+        "UPDATE webhook_deliveries
+         SET status = ?2, attempts = attempts + 1, next_attempt_at = ?3, last_attempt_at = ?4,
+             response_status = ?5, last_error = ?6
+         WHERE id = ?1",
+        params![delivery_id, status, outcome.retry_at, outcome.attempted_at, outcome.response_status, outcome.error],
+    )?;
+    Ok(())
+}
+
+/// The most recent deliveries, newest first.
+pub fn read_recent_deliveries(conn: &Connection, limit: u32) -> RusqliteResult<Vec<WebhookDelivery>> {
+    let mut stmt = conn.prepare(
+        "SELECT d.id, d.webhook_id, w.url, d.event, d.status, d.attempts, d.next_attempt_at,
+                d.last_attempt_at, d.response_status, d.last_error, d.created_at
+         FROM webhook_deliveries d JOIN webhooks w ON w.id = d.webhook_id
+         ORDER BY d.id DESC LIMIT ?1",
+    )?;
+    let rows = stmt.query_map([limit], |row| {
+        Ok(WebhookDelivery {
+            id: row.get(0)?,
+            webhook_id: row.get(1)?,
+            webhook_url: row.get(2)?,
+            event: row.get(3)?,
+            status: row.get(4)?,
+            attempts: row.get(5)?,
+            next_attempt_at: row.get(6)?,
+            last_attempt_at: row.get(7)?,
+            response_status: row.get(8)?,
+            last_error: row.get(9)?,
+            created_at: row.get(10)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Queues the payload of an earlier delivery again, as a new delivery.
+/// Returns the new delivery id, or `None` if the delivery is not in the log.
+pub fn redeliver(conn: &Connection, delivery_id: i64, now: &str) -> RusqliteResult<Option<i64>> {
+    let original: Option<(i64, String, String)> = conn
+        .query_row(
+            "SELECT webhook_id, event, payload FROM webhook_deliveries WHERE id = ?1",
+            [delivery_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?;
+    let Some((webhook_id, event, payload)) = original else { return Ok(None) };
+
+    conn.execute(
+        "INSERT INTO webhook_deliveries (webhook_id, event, payload, status, next_attempt_at, created_at)
+         VALUES (?1, ?2, ?3, 'pending', ?4, ?4)",
+        params![webhook_id, event, payload, now],
+    )?;
+    Ok(Some(conn.last_insert_rowid()))
+}
+
+/// Drops finished deliveries created before `before`. Returns the number removed.
+pub fn prune_deliveries(conn: &Connection, before: &str) -> RusqliteResult<usize> {
+    conn.execute(
+        "DELETE FROM webhook_deliveries WHERE status != 'pending' AND created_at < ?1",
+        [before],
+    )
+}
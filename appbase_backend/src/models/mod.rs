@@ -2,6 +2,8 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
 
 #[derive(Serialize, Deserialize)]
 pub struct EditLogEntry {
@@ -10,7 +12,8 @@ pub struct EditLogEntry {
     pub edited_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Everything about a post except its body.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct PostMetadata {
     pub title: String,
     pub created_at: DateTime<Utc>,
@@ -20,23 +23,252 @@ pub struct PostMetadata {
     pub cover_image: Option<String>,
     pub has_call_to_action: Option<bool>,
     pub search_keywords: Option<Vec<String>>, 
+    // NEW: Assigned when the post is approved; older slugs keep resolving via SLUG_INDEX.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    // NEW: Derived from the body when the post is published; absent on pending posts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word_count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reading_time_minutes: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub toc: Vec<TocEntry>,
+}
+
+/// Reading statistics and table of contents of a published post, served by `/api/posts/{id}/toc`.
+#[derive(Serialize, ToSchema)]
+pub struct PostOutline {
+    pub id: String,
+    pub word_count: u32,
+    pub reading_time_minutes: u32,
+    pub toc: Vec<TocEntry>,
 }
 
-#[derive(Serialize)]
+/// One heading of a post's table of contents. `anchor` is the `id` of the heading
+/// in the rendered HTML.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct TocEntry {
+    pub level: u8,
+    pub text: String,
+    pub anchor: String,
+}
+
+/// A published post including its markdown body.
+#[derive(Serialize, Clone, ToSchema)]
 pub struct FullPost {
     pub id: String,
     pub metadata: PostMetadata,
+    /// The markdown body as stored: HTML-entity-escaped (`&lt;`, `&amp;`, ...).
     pub content: String,
+    // NEW: Reaction counts per configured kind. Filled in by the public API; absent elsewhere.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reactions: Option<BTreeMap<String, u64>>,
 }
 
-#[derive(Serialize, Clone)]
+/// A post as it appears in lists: metadata without the body.
+#[derive(Serialize, Clone, ToSchema)]
 pub struct PostSummary {
     pub id: String,
     pub metadata: PostMetadata,
+    // NEW: Filled in by the public API from `post_ownership`; absent elsewhere.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<AuthorInfo>,
+}
+
+/// The byline attached to public post summaries.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct AuthorInfo {
+    pub username: String,
+    pub display_name: String,
+    pub avatar_url: Option<String>,
+}
+
+/// One link on a contributor profile, e.g. a personal site or social account.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ProfileLink {
+    pub label: String,
+    pub url: String,
+}
+
+/// The editable part of a contributor's public profile (`contributor_profiles` table).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct ContributorProfile {
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_media_id: Option<String>,
+    #[serde(default)]
+    pub links: Vec<ProfileLink>,
+}
+
+/// A public author page served by `/api/authors`. `display_name` falls back to the username.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthorProfile {
+    pub username: String,
+    pub display_name: String,
+    pub bio: Option<String>,
+    pub avatar_media_id: Option<String>,
+    pub avatar_url: Option<String>,
+    pub links: Vec<ProfileLink>,
+    pub post_count: u64,
+}
+
+/// Shared envelope for every paginated list endpoint, in the spirit of
+/// `advanced_db_manager_models::PaginatedResponse`. Offset-paginated responses
+/// fill `offset`; keyset-paginated ones fill `next_cursor` instead.
+#[derive(Serialize, ToSchema)]
+pub struct PaginatedList<T: Serialize> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub limit: u32,
+    pub offset: Option<u32>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+impl<T: Serialize> PaginatedList<T> {
+    pub fn from_offset(items: Vec<T>, total: u64, limit: u32, offset: u32) -> Self {
+        PaginatedList {
+            items,
+            total,
+            limit,
+            offset: Some(offset),
+            next_cursor: None,
+            has_more: (offset as u64 + limit as u64) < total,
+        }
+    }
+
+    pub fn from_cursor(items: Vec<T>, total: u64, limit: u32, next_cursor: Option<String>) -> Self {
+        PaginatedList {
+            items,
+            total,
+            limit,
+            offset: None,
+            has_more: next_cursor.is_some(),
+            next_cursor,
+        }
+    }
+}
+
+/// One node of the hierarchical tag taxonomy served by `/api/tags/tree`.
+/// `post_count` includes posts tagged with any descendant path.
+#[derive(Serialize, Clone, ToSchema)]
+pub struct TagTreeNode {
+    pub name: String,
+    pub path: String,
+    pub post_count: u64,
+    #[schema(no_recursion)]
+    pub children: Vec<TagTreeNode>,
+}
+
+/// Number of published posts in one calendar month (UTC), served by `/api/posts/archive`.
+#[derive(Serialize, Clone, ToSchema)]
+pub struct ArchiveMonth {
+    pub year: i32,
+    pub month: u32,
+    pub post_count: u64,
+}
+
+/// A tag or search keyword completion, with the number of published posts that carry it.
+#[derive(Serialize, Clone, ToSchema)]
+pub struct TermSuggestion {
+    pub value: String,
+    pub post_count: u64,
+}
+
+/// A published post whose title matches the typed prefix.
+#[derive(Serialize, Clone, ToSchema)]
+pub struct TitleSuggestion {
+    pub id: String,
+    pub title: String,
+    pub slug: Option<String>,
+}
+
+/// Search-as-you-type completions served by `/api/suggest`.
+#[derive(Serialize, Clone, Default, ToSchema)]
+pub struct Suggestions {
+    pub tags: Vec<TermSuggestion>,
+    /// Only filled in when `types` includes `vocabulary`.
+    pub vocabulary: Vec<TermSuggestion>,
+    pub keywords: Vec<TermSuggestion>,
+    pub titles: Vec<TitleSuggestion>,
+}
+
+/// A published post with its views in the requested window, served by `/api/posts/popular`.
+#[derive(Serialize, ToSchema)]
+pub struct PopularPost {
+    pub views: u64,
+    pub post: PostSummary,
+}
+
+/// Views of a post on one UTC day (`YYYY-MM-DD`).
+#[derive(Serialize, Clone, ToSchema)]
+pub struct DailyViews {
+    pub date: String,
+    pub views: u64,
+}
+
+/// View statistics of one post for the contributor dashboard. `daily` covers the
+/// requested number of days up to today, including days without views.
+#[derive(Serialize, ToSchema)]
+pub struct PostViewStats {
+    pub post_id: String,
+    pub total_views: u64,
+    pub last_7_days: u64,
+    pub last_30_days: u64,
+    pub daily: Vec<DailyViews>,
+}
+
+/// A reader comment on a published post. `content` is stored escaped, like post bodies;
+/// `depth` is 0 for top-level comments and grows by one per reply level.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct Comment {
+    pub id: String,
+    pub post_id: String,
+    pub parent_id: Option<String>,
+    pub depth: u8,
+    pub author_name: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An approved comment with its approved replies, oldest first, served by `/api/posts/{id}/comments`.
+#[derive(Serialize, Clone, ToSchema)]
+pub struct CommentThread {
+    pub id: String,
+    pub parent_id: Option<String>,
+    pub depth: u8,
+    pub author_name: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    #[schema(no_recursion)]
+    pub replies: Vec<CommentThread>,
+}
+
+/// A comment awaiting moderation, with the title of the post it was left on.
+#[derive(Serialize, ToSchema)]
+pub struct PendingCommentWithPost {
+    pub comment: Comment,
+    pub post_title: String,
+}
+
+/// A reaction token from `/api/reactions/token`. It is valid until `expires_at`;
+/// afterwards clients fetch a new one.
+#[derive(Serialize, ToSchema)]
+pub struct ReactionToken {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// The outcome of a reaction. `counted` is false if the token had already given it.
+#[derive(Serialize, ToSchema)]
+pub struct ReactionResult {
+    pub kind: String,
+    pub counted: bool,
+    pub reactions: BTreeMap<String, u64>,
 }
 
 // --- NEW STRUCT ---
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct PendingPostSummaryWithOwner {
     pub post_summary: PostSummary,
     pub author_name: String,
@@ -63,7 +295,34 @@ pub struct Notification {
     pub r#type: String, // 'success' or 'error'
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// A webhook endpoint registered by the admin. `events` lists the event names it receives.
+#[derive(Debug, Serialize, Clone)]
+pub struct Webhook {
+    pub id: i64,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<String>,
+    pub created_at: String,
+}
+
+/// One entry of the webhook delivery log. `status` is `pending`, `delivered` or `failed`.
+#[derive(Debug, Serialize, Clone)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub webhook_url: String,
+    pub event: String,
+    pub status: String,
+    pub attempts: u32,
+    pub next_attempt_at: Option<String>,
+    pub last_attempt_at: Option<String>,
+    pub response_status: Option<u16>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+}
+
+/// An uploaded media file as listed in the contributor media library.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct MediaAttachment {
     pub id: String,
     pub file_path: String,
@@ -1,12 +1,14 @@
 
-use crate::helper::{admin_helpers, public_helpers};
+use crate::helper::{admin_helpers, comment_helpers, public_helpers, reaction_helpers};
+use crate::helper::webhook_helpers::{self, WebhookError, WebhookEvent};
 use crate::middleware::AuthenticatedContributor;
+use crate::middleware::rate_limit::{RateLimit, RateLimitScope};
 use crate::models::Notification;
 use crate::config::Config;
 use crate::AppState;
 use crate::routes::advanced_db_manager;
 use actix_session::Session;
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{guard, web, HttpResponse, Responder};
 use redb::Database;
 //use rusqlite::Connection;
 use tera::{Context, Tera};
@@ -32,7 +34,12 @@ impl CsrfGuarded for LoginForm {
 // ... (keep config_login, config_dashboard, and set_notification functions)
 pub fn config_login(cfg: &mut web::ServiceConfig) {
     cfg.route("/login", web::get().to(show_admin_login_form))
-        .route("/login", web::post().to(handle_admin_login))
+        .service(
+            web::resource("/login")
+                .guard(guard::Post())
+                .wrap(RateLimit(RateLimitScope::Login))
+                .to(handle_admin_login),
+        )
         .route("/logout", web::post().to(handle_admin_logout));
 }
 
@@ -44,9 +51,19 @@ pub fn config_dashboard(cfg: &mut web::ServiceConfig) {
         .route("/update_settings", web::post().to(update_settings_action))
         .route("/add_tag", web::post().to(add_tag_action))
         .route("/delete_tag", web::post().to(delete_tag_action))
+        .route("/approve_comment", web::post().to(approve_comment_action))
+        .route("/delete_comment", web::post().to(delete_comment_action))
+        .route("/add_webhook", web::post().to(add_webhook_action))
+        .route("/delete_webhook", web::post().to(delete_webhook_action))
+        .route("/redeliver_webhook", web::post().to(redeliver_webhook_action))
         .configure(advanced_db_manager::config_advanced_db_manager);
 }
 
+// Oldest pending comments listed on the dashboard; the rest follow once these are handled.
+const PENDING_COMMENTS_SHOWN: u32 = 25;
+// Newest webhook deliveries listed in the dashboard's delivery log.
+const WEBHOOK_DELIVERIES_SHOWN: u32 = 50;
+
 fn set_notification(session: &Session, message: &str, r#type: &str) {
     session.insert("notification", &Notification { message: message.to_string(), r#type: r#type.to_string() }).unwrap();
 }
@@ -69,6 +86,13 @@ async fn update_settings_action(
     let prefix = parsed.get("contributor_path_prefix").map(|s| s.trim()).unwrap_or("");
     let max_size = parsed.get("max_file_upload_size_mb").map(|s| s.trim()).unwrap_or("10");
     let mime_types = parsed.get("allowed_mime_types").map(|s| s.trim()).unwrap_or("");
+    let reaction_kinds = match reaction_helpers::parse_reaction_kinds(parsed.get("reaction_kinds").map(|s| s.as_str()).unwrap_or("")) {
+        Ok(kinds) => kinds,
+        Err(message) => {
+            set_notification(&session, &message, "error");
+            return HttpResponse::Found().append_header(("location", dashboard_url)).finish();
+        }
+    };
 
     let is_prefix_valid = !prefix.is_empty() && prefix.chars().all(|c| c.is_alphanumeric() || c == '-');
     let is_max_size_valid = max_size.parse::<u64>().is_ok();
@@ -77,9 +101,10 @@ async fn update_settings_action(
         let update_prefix_res = admin_helpers::update_setting(&pool, "contributor_path_prefix", prefix);
         let update_size_res = admin_helpers::update_setting(&pool, "max_file_upload_size_mb", max_size);
         let update_mimes_res = admin_helpers::update_setting(&pool, "allowed_mime_types", mime_types);
+        let update_kinds_res = admin_helpers::update_setting(&pool, "reaction_kinds", &reaction_kinds.join(","));
         
-        match (update_prefix_res, update_size_res, update_mimes_res) {
-            (Ok(_), Ok(_), Ok(_)) => {
+        match (update_prefix_res, update_size_res, update_mimes_res, update_kinds_res) {
+            (Ok(_), Ok(_), Ok(_), Ok(_)) => {
                 // --- MODIFIED BLOCK: Safely handle potential RwLock poisoning ---
                 let mut state_prefix = app_state.contributor_prefix.write().unwrap_or_else(|poisoned| {
                     log::error!("RwLock for contributor_prefix was poisoned during settings update! Recovering lock.");
@@ -87,6 +112,7 @@ async fn update_settings_action(
                 });
                 // --- END MODIFICATION ---
                 *state_prefix = prefix.to_string();
+                *app_state.reaction_kinds.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = reaction_kinds;
                 set_notification(&session, "Settings updated successfully.", "success");
             },
             _ => {
@@ -200,6 +226,7 @@ async fn show_admin_dashboard(
                 contributor_path_prefix: "error-loading".to_string(),
                 max_file_upload_size_mb: "0".to_string(),
                 allowed_mime_types: "".to_string(),
+                reaction_kinds: "".to_string(),
             }
         }
     };
@@ -225,6 +252,24 @@ async fn show_admin_dashboard(
         }
     }
 
+    ctx.insert("read_cache", &admin_helpers::get_read_cache_stats());
+
+    match comment_helpers::fetch_pending_comments(&db, PENDING_COMMENTS_SHOWN, 0) {
+        Ok(pending) => ctx.insert("pending_comments", &pending),
+        Err(e) => log::error!("Failed to fetch pending comments: {}", e),
+    }
+
+    let webhook_events: Vec<&str> = WebhookEvent::ALL.iter().map(|event| event.as_str()).collect();
+    ctx.insert("webhook_events", &webhook_events);
+    match webhook_helpers::fetch_webhooks(&pool) {
+        Ok(webhooks) => ctx.insert("webhooks", &webhooks),
+        Err(e) => log::error!("Failed to fetch webhooks: {}", e),
+    }
+    match webhook_helpers::fetch_recent_deliveries(&pool, WEBHOOK_DELIVERIES_SHOWN) {
+        Ok(deliveries) => ctx.insert("webhook_deliveries", &deliveries),
+        Err(e) => log::error!("Failed to fetch webhook deliveries: {}", e),
+    }
+
     match tera.render("admin/dashboard.html", &ctx) {
         Ok(rendered) => HttpResponse::Ok().content_type("text/html; charset=utf-8").body(rendered),
         Err(err) => {
@@ -432,4 +477,138 @@ async fn delete_tag_action(
         }
     }
     HttpResponse::Found().append_header(("location", dashboard_url)).finish()
-}
\ No newline at end of file
+}
+async fn approve_comment_action(
+    session: Session,
+    db: web::Data<Database>,
+    form: web::Bytes,
+    config: web::Data<Config>,
+) -> impl Responder {
+    let dashboard_url = format!("/management/{}/dashboard", &config.admin_url_prefix);
+
+    let parsed = match crate::helper::form_helpers::parse_form(&form) {
+        Ok(p) => p,
+        Err(response) => return response,
+    };
+
+    if let Some(comment_id) = parsed.get("comment_id").map(|s| s.trim()) {
+        match comment_helpers::approve_comment(&db, comment_id) {
+            Ok(_) => set_notification(&session, "Comment approved.", "success"),
+            Err(e) => {
+                log::error!("Failed to approve comment '{}': {}", comment_id, e);
+                set_notification(&session, "Failed to approve comment. It may have been handled already.", "error");
+            }
+        }
+    }
+    HttpResponse::Found().append_header(("location", dashboard_url)).finish()
+}
+
+async fn delete_comment_action(
+    session: Session,
+    db: web::Data<Database>,
+    form: web::Bytes,
+    config: web::Data<Config>,
+) -> impl Responder {
+    let dashboard_url = format!("/management/{}/dashboard", &config.admin_url_prefix);
+
+    let parsed = match crate::helper::form_helpers::parse_form(&form) {
+        Ok(p) => p,
+        Err(response) => return response,
+    };
+
+    if let Some(comment_id) = parsed.get("comment_id").map(|s| s.trim()) {
+        match comment_helpers::delete_comment(&db, comment_id) {
+            Ok(removed) => set_notification(&session, &format!("Deleted {} comment(s).", removed), "success"),
+            Err(e) => {
+                log::error!("Failed to delete comment '{}': {}", comment_id, e);
+                set_notification(&session, "Failed to delete comment. Check the comment ID.", "error");
+            }
+        }
+    }
+    HttpResponse::Found().append_header(("location", dashboard_url)).finish()
+}
+
+async fn add_webhook_action(
+    session: Session,
+    pool: web::Data<crate::DbPool>,
+    form: web::Bytes,
+    config: web::Data<Config>,
+) -> impl Responder {
+    let dashboard_url = format!("/management/{}/dashboard", &config.admin_url_prefix);
+
+    let parsed = match crate::helper::form_helpers::parse_form(&form) {
+        Ok(p) => p,
+        Err(response) => return response,
+    };
+
+    let url = parsed.get("url").map(|s| s.trim()).unwrap_or("");
+    let secret = parsed.get("secret").map(|s| s.trim()).unwrap_or("");
+    // One checkbox per event, named `event_<name>`.
+    let events: Vec<&str> = WebhookEvent::ALL
+        .iter()
+        .map(|event| event.as_str())
+        .filter(|name| parsed.contains_key(&format!("event_{}", name)))
+        .collect();
+
+    match webhook_helpers::create_webhook(&pool, url, secret, &events) {
+        Ok(secret) => set_notification(&session, &format!("Webhook added. Its signing secret is: {}", secret), "success"),
+        Err(WebhookError::Invalid(message)) => set_notification(&session, &message, "error"),
+        Err(e) => {
+            log::error!("Failed to add webhook '{}': {}", url, e);
+            set_notification(&session, "Failed to add webhook.", "error");
+        }
+    }
+    HttpResponse::Found().append_header(("location", dashboard_url)).finish()
+}
+
+async fn delete_webhook_action(
+    session: Session,
+    pool: web::Data<crate::DbPool>,
+    form: web::Bytes,
+    config: web::Data<Config>,
+) -> impl Responder {
+    let dashboard_url = format!("/management/{}/dashboard", &config.admin_url_prefix);
+
+    let parsed = match crate::helper::form_helpers::parse_form(&form) {
+        Ok(p) => p,
+        Err(response) => return response,
+    };
+
+    if let Some(webhook_id) = parsed.get("webhook_id").and_then(|s| s.trim().parse::<i64>().ok()) {
+        match webhook_helpers::delete_webhook(&pool, webhook_id) {
+            Ok(0) => set_notification(&session, "Webhook not found.", "error"),
+            Ok(_) => set_notification(&session, "Webhook and its delivery log deleted.", "success"),
+            Err(e) => {
+                log::error!("Failed to delete webhook {}: {}", webhook_id, e);
+                set_notification(&session, "Failed to delete webhook.", "error");
+            }
+        }
+    }
+    HttpResponse::Found().append_header(("location", dashboard_url)).finish()
+}
+
+async fn redeliver_webhook_action(
+    session: Session,
+    pool: web::Data<crate::DbPool>,
+    form: web::Bytes,
+    config: web::Data<Config>,
+) -> impl Responder {
+    let dashboard_url = format!("/management/{}/dashboard", &config.admin_url_prefix);
+
+    let parsed = match crate::helper::form_helpers::parse_form(&form) {
+        Ok(p) => p,
+        Err(response) => return response,
+    };
+
+    if let Some(delivery_id) = parsed.get("delivery_id").and_then(|s| s.trim().parse::<i64>().ok()) {
+        match webhook_helpers::redeliver(&pool, delivery_id) {
+            Ok(Some(new_id)) => set_notification(&session, &format!("Delivery {} queued again as delivery {}.", delivery_id, new_id), "success"),
+            Ok(None) => set_notification(&session, "Delivery not found. It may have expired from the log.", "error"),
+            Err(e) => {
+                log::error!("Failed to redeliver webhook delivery {}: {}", delivery_id, e);
+                set_notification(&session, "Failed to queue the delivery again.", "error");
+            }
+        }
+    }
+    HttpResponse::Found().append_header(("location", dashboard_url)).finish()
+}
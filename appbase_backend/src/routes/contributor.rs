@@ -1,17 +1,20 @@
-use crate::helper::{contributor_helpers, public_helpers};
+use crate::helper::{comment_helpers, contributor_helpers, public_helpers};
 use crate::middleware::AuthenticatedContributor;
+use crate::middleware::rate_limit::{RateLimit, RateLimitScope};
+use crate::models::db_operations::posts_db_operations::DbError;
 use crate::models::db_operations::users_db_operations;
-use crate::models::{MediaAttachment, PostSummary, Contributor, PostAction};
+use crate::models::{MediaAttachment, PostSummary, Contributor, ContributorProfile, PostAction, FullPost, PaginatedList, PendingPostSummaryWithOwner, PostViewStats, DailyViews, Comment, PendingCommentWithPost};
 use crate::config::Config;
 use crate::AppState;
 use actix_session::Session;
-use actix_web::{web, HttpResponse, Responder, Error};
+use actix_web::{guard, web, HttpResponse, Responder, Error};
 use actix_multipart::Multipart;
 use redb::Database;
 //use rusqlite::Connection;
 use tera::{Context, Tera};
 //use url::form_urlencoded;
 use serde::Serialize;
+use utoipa::{IntoParams, OpenApi, ToSchema};
 use serde_json::json;
 use actix_csrf::extractor::{Csrf, CsrfGuarded, CsrfToken};
 use serde::Deserialize;
@@ -29,20 +32,25 @@ impl CsrfGuarded for LoginForm {
     fn csrf_token(&self) -> &CsrfToken { &self.csrf_token }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct SimilarCheckPayload {
     title: String,
+    /// Comma-separated tags.
     tags: String,
+    /// `title`, `tags` or `both`.
     check_type: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct PaginationQuery {
+    /// 1-based page number.
     page: Option<u32>,
+    /// Page size.
     limit: Option<u32>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct FullPostUpdateRequest {
     title: String,
     summary: String,
@@ -53,29 +61,56 @@ struct FullPostUpdateRequest {
     has_call_to_action: Option<bool>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct PostSearchQuery {
+    /// `post_id`, `tag`, `title` or `keyword`.
     search_type: String,
     q: String,
+    /// 1-based page number.
     page: Option<u32>,
+    /// Page size.
     limit: Option<u32>,
+    /// Typo-tolerant matching for `tag`, `title` and `keyword` searches, closest match first.
+    fuzzy: Option<bool>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct StatsQuery {
+    /// Number of days in `daily`, ending today (default 30, at most 365).
+    days: Option<u32>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SearchQuery {
+    /// Media tag to search for.
     q: String,
+    /// 1-based page number.
     page: Option<u32>,
+    /// Page size.
     limit: Option<u32>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct ApiResponse<T: Serialize> {
     success: bool,
     data: Option<T>,
     error: Option<String>,
 }
 
-#[derive(Deserialize)]
+/// Shape of the `json!({"success", "message" | "error"})` replies of the write endpoints.
+/// Only used to document them in the OpenAPI spec.
+#[derive(ToSchema)]
+#[allow(dead_code)]
+struct ApiMessage {
+    success: bool,
+    message: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
 struct ApproveRequest {
     confirmation: String,
 }
@@ -84,7 +119,12 @@ struct ApproveRequest {
 // --- Route Configuration ---
 pub fn config_login(cfg: &mut web::ServiceConfig) {
     cfg.route("/login", web::get().to(show_contributor_login_form))
-        .route("/login", web::post().to(handle_contributor_login))
+        .service(
+            web::resource("/login")
+                .guard(guard::Post())
+                .wrap(RateLimit(RateLimitScope::Login))
+                .to(handle_contributor_login),
+        )
         .route("/logout", web::post().to(handle_contributor_logout));
 }
 
@@ -94,18 +134,34 @@ pub fn config_dashboard(cfg: &mut web::ServiceConfig) {
         .route("/approve", web::get().to(show_approve_page))
         .route("/submit_post", web::post().to(submit_post_action)) // Renamed from create_post
         .route("/delete_post", web::post().to(delete_post_action))
-        .route("/upload_media", web::post().to(upload_media_action))
+        .service(
+            web::resource("/upload_media")
+                .guard(guard::Post())
+                .wrap(RateLimit(RateLimitScope::Uploads))
+                .to(upload_media_action),
+        )
         .route("/delete_media", web::post().to(delete_media_action))
         .service(
             web::scope("/api")
                 .route("/mymedia", web::get().to(get_my_media_action))
                 .route("/myposts", web::get().to(get_my_posts_action))
-                .route("/media/search", web::get().to(search_media_action))
+                .service(
+                    web::resource("/media/search")
+                        .guard(guard::Get())
+                        .wrap(RateLimit(RateLimitScope::Search))
+                        .to(search_media_action),
+                )
                 .route("/tags", web::get().to(get_available_tags_action))
                 .route("/posts/check_similar", web::post().to(check_similar_posts_action))
-                .route("/posts/search", web::get().to(search_posts_action))
+                .service(
+                    web::resource("/posts/search")
+                        .guard(guard::Get())
+                        .wrap(RateLimit(RateLimitScope::Search))
+                        .to(search_posts_action),
+                )
                 .route("/posts/{post_id}", web::get().to(get_post_details_api)) // NEW: Get published post details
                 .route("/posts/{post_id}/update", web::post().to(update_full_post_action))
+                .route("/posts/{post_id}/stats", web::get().to(get_post_stats_api))
                 // --- NEW API Endpoints ---
                 .route("/pending", web::get().to(get_pending_posts_api))
                 .route("/pending/{post_id}", web::get().to(get_pending_post_details_api))
@@ -115,10 +171,49 @@ pub fn config_dashboard(cfg: &mut web::ServiceConfig) {
                 .route("/mypending/{post_id}", web::get().to(get_my_pending_post_details_api)) // NEW: Get own pending post details
                 .route("/mypending/{post_id}/update", web::post().to(update_my_pending_post_api)) // NEW: Update own pending post
                 .route("/mypending/{post_id}/delete", web::post().to(delete_my_pending_post_api))
+                // --- Comment moderation ---
+                .route("/comments/pending", web::get().to(get_pending_comments_api))
+                .route("/comments/{comment_id}/approve", web::post().to(approve_comment_api))
+                .route("/comments/{comment_id}/delete", web::post().to(delete_comment_api))
+                // --- Public profile ---
+                .route("/profile", web::get().to(get_my_profile_api))
+                .route("/profile/update", web::post().to(update_my_profile_api))
         );
 }
 
 
+/// OpenAPI description of the contributor `/api` scope; served as part of `/api/openapi.json`.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        get_my_media_action,
+        get_my_posts_action,
+        search_media_action,
+        get_available_tags_action,
+        check_similar_posts_action,
+        search_posts_action,
+        get_post_details_api,
+        get_post_stats_api,
+        update_full_post_action,
+        get_pending_posts_api,
+        get_pending_post_details_api,
+        approve_post_api,
+        delete_pending_post_api,
+        get_my_pending_posts_api,
+        get_my_pending_post_details_api,
+        update_my_pending_post_api,
+        delete_my_pending_post_api,
+        get_pending_comments_api,
+        approve_comment_api,
+        delete_comment_api,
+        get_my_profile_api,
+        update_my_profile_api,
+    ),
+    components(schemas(MediaAttachment, ContributorProfile, PendingPostSummaryWithOwner, PostViewStats, DailyViews, Comment, PendingCommentWithPost, ApiMessage)),
+    tags((name = "contributor", description = "Dashboard API for logged-in contributors. Requires the session cookie; POST requests also need the `X-CSRF-Token` header.")),
+)]
+pub struct ContributorApiDoc;
+
 // --- Utility to get current user details ---
 fn get_current_user(auth_user: &AuthenticatedContributor, pool: &web::Data<crate::DbPool>) -> Result<Contributor, HttpResponse> {
     contributor_helpers::get_contributor_details(pool, &auth_user.username)
@@ -359,6 +454,18 @@ async fn delete_media_action(
 }
 
 // --- API Handlers ---
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/mymedia",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "Media uploaded by the current contributor.", body = ApiResponse<Vec<MediaAttachment>>),
+    ),
+)]
 async fn get_my_media_action( auth_user: AuthenticatedContributor, pool: web::Data<crate::DbPool>, config: web::Data<Config> ) -> impl Responder {
     let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
     match contributor_helpers::get_user_media(&config, &pool, user.id) {
@@ -367,6 +474,19 @@ async fn get_my_media_action( auth_user: AuthenticatedContributor, pool: web::Da
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/myposts",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        PaginationQuery,
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "The contributor's published posts.", body = ApiResponse<PaginatedList<PostSummary>>),
+    ),
+)]
 async fn get_my_posts_action( auth_user: AuthenticatedContributor, db: web::Data<Database>, pool: web::Data<crate::DbPool>, query: web::Query<PaginationQuery> ) -> impl Responder {
     let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
     let page = query.page.unwrap_or(1).max(1); // <-- FIX APPLIED
@@ -381,6 +501,21 @@ async fn get_my_posts_action( auth_user: AuthenticatedContributor, db: web::Data
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/media/search",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        SearchQuery,
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "Media matching the tag query.", body = ApiResponse<PaginatedList<MediaAttachment>>),
+        (status = 400, description = "Invalid request.", body = ApiMessage),
+        (status = 429, description = "Rate limit exceeded; see `Retry-After`."),
+    ),
+)]
 async fn search_media_action( config: web::Data<Config>, pool: web::Data<crate::DbPool>, query: web::Query<SearchQuery> ) -> impl Responder {
     let search_term = query.q.trim();
     let page = query.page.unwrap_or(1).max(1); // <-- FIX APPLIED
@@ -394,6 +529,19 @@ async fn search_media_action( config: web::Data<Config>, pool: web::Data<crate::
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/management/{prefix}/api/posts/check_similar",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+    ),
+    request_body = SimilarCheckPayload,
+    security(("session_cookie" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "Published posts similar to the draft.", body = ApiResponse<Vec<PostSummary>>),
+    ),
+)]
 async fn check_similar_posts_action( db: web::Data<Database>, payload: web::Json<SimilarCheckPayload> ) -> impl Responder {
     match contributor_helpers::check_similar_posts( &db, &payload.title, &payload.tags, &payload.check_type, None ) {
         Ok(posts) => HttpResponse::Ok().json(ApiResponse { success: true, data: Some(posts), error: None }),
@@ -404,6 +552,21 @@ async fn check_similar_posts_action( db: web::Data<Database>, payload: web::Json
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/management/{prefix}/api/posts/{post_id}/update",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        ("post_id" = String, Path, description = "Post id."),
+    ),
+    request_body = FullPostUpdateRequest,
+    security(("session_cookie" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "The edit was re-submitted for approval.", body = ApiMessage),
+        (status = 403, description = "Not allowed for this contributor.", body = ApiMessage),
+    ),
+)]
 async fn update_full_post_action(
     auth_user: AuthenticatedContributor,
     path_params: web::Path<(String, String)>,
@@ -427,6 +590,18 @@ async fn update_full_post_action(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/tags",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "The tag vocabulary.", body = ApiResponse<Vec<String>>),
+    ),
+)]
 async fn get_available_tags_action( db: web::Data<Database> ) -> impl Responder {
     match contributor_helpers::get_all_available_tags(&db) {
         Ok(tags) => HttpResponse::Ok().json(ApiResponse { success: true, data: Some(tags), error: None }),
@@ -437,6 +612,21 @@ async fn get_available_tags_action( db: web::Data<Database> ) -> impl Responder
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/posts/search",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        PostSearchQuery,
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "Matching published posts.", body = ApiResponse<PaginatedList<PostSummary>>),
+        (status = 400, description = "Invalid request.", body = ApiMessage),
+        (status = 429, description = "Rate limit exceeded; see `Retry-After`."),
+    ),
+)]
 async fn search_posts_action( db: web::Data<Database>, query: web::Query<PostSearchQuery> ) -> impl Responder {
     let search_term = query.q.trim();
     let search_type = query.search_type.as_str();
@@ -446,7 +636,7 @@ async fn search_posts_action( db: web::Data<Database>, query: web::Query<PostSea
     if search_term.is_empty() {
         return HttpResponse::BadRequest().json(ApiResponse { success: false, data: None::<Vec<PostSummary>>, error: Some("Search query cannot be empty.".to_string()) });
     }
-    match contributor_helpers::search_posts(&db, search_type, search_term, limit, offset) {
+    match contributor_helpers::search_posts(&db, search_type, search_term, query.fuzzy.unwrap_or(false), limit, offset) {
         Ok(posts) => HttpResponse::Ok().json(ApiResponse { success: true, data: Some(posts), error: None }),
         Err(e) => {
             log::error!("Failed to search posts: {}", e);
@@ -457,6 +647,20 @@ async fn search_posts_action( db: web::Data<Database>, query: web::Query<PostSea
 
 // --- NEW API HANDLERS for Approval Workflow ---
 
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/pending",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        PaginationQuery,
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "Posts awaiting approval (approvers only).", body = ApiResponse<PaginatedList<PendingPostSummaryWithOwner>>),
+        (status = 403, description = "Not allowed for this contributor.", body = ApiMessage),
+    ),
+)]
 async fn get_pending_posts_api( auth_user: AuthenticatedContributor, db: web::Data<Database>, pool: web::Data<crate::DbPool>, query: web::Query<PaginationQuery> ) -> impl Responder {
     let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
     if !user.can_approve_posts {
@@ -475,6 +679,120 @@ async fn get_pending_posts_api( auth_user: AuthenticatedContributor, db: web::Da
     }
 }
 
+/// Reader comments awaiting moderation, oldest first.
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/comments/pending",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        PaginationQuery,
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "One page of the moderation queue.", body = ApiResponse<PaginatedList<PendingCommentWithPost>>),
+        (status = 403, description = "The contributor may not approve posts.", body = ApiMessage),
+    ),
+)]
+async fn get_pending_comments_api(auth_user: AuthenticatedContributor, db: web::Data<Database>, pool: web::Data<crate::DbPool>, query: web::Query<PaginationQuery>) -> impl Responder {
+    let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
+    if !user.can_approve_posts {
+        return HttpResponse::Forbidden().json(ApiResponse { success: false, data: None::<()>, error: Some("Permission denied.".to_string()) });
+    }
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(10);
+    let offset = (page - 1) * limit;
+
+    match comment_helpers::fetch_pending_comments(&db, limit, offset) {
+        Ok(comments) => HttpResponse::Ok().json(ApiResponse { success: true, data: Some(comments), error: None }),
+        Err(e) => {
+            log::error!("Failed to fetch pending comments: {}", e);
+            HttpResponse::InternalServerError().json(ApiResponse { success: false, data: None::<()>, error: Some("Failed to retrieve pending comments.".to_string()) })
+        }
+    }
+}
+
+/// Maps a failed comment moderation action to a response.
+fn comment_action_error(comment_id: &str, e: DbError) -> HttpResponse {
+    match e {
+        DbError::NotFound(_) | DbError::Uuid(_) => HttpResponse::NotFound().json(json!({"success": false, "error": "Comment not found."})),
+        e => {
+            log::error!("Failed to moderate comment {}: {}", comment_id, e);
+            HttpResponse::InternalServerError().json(json!({"success": false, "error": "Failed to update the comment."}))
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/management/{prefix}/api/comments/{comment_id}/approve",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        ("comment_id" = String, Path, description = "Comment id."),
+    ),
+    security(("session_cookie" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "The comment is public.", body = ApiMessage),
+        (status = 403, description = "The contributor may not approve posts.", body = ApiMessage),
+        (status = 404, description = "No such pending comment.", body = ApiMessage),
+    ),
+)]
+async fn approve_comment_api(auth_user: AuthenticatedContributor, db: web::Data<Database>, pool: web::Data<crate::DbPool>, path: web::Path<(String, String)>) -> impl Responder {
+    let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
+    if !user.can_approve_posts {
+        return HttpResponse::Forbidden().json(json!({"success": false, "error": "Permission denied."}));
+    }
+    let comment_id = path.into_inner().1;
+    match comment_helpers::approve_comment(&db, &comment_id) {
+        Ok(()) => HttpResponse::Ok().json(json!({"success": true, "message": "Comment approved."})),
+        Err(e) => comment_action_error(&comment_id, e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/management/{prefix}/api/comments/{comment_id}/delete",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        ("comment_id" = String, Path, description = "Comment id."),
+    ),
+    security(("session_cookie" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "The comment and its replies were removed.", body = ApiMessage),
+        (status = 403, description = "The contributor may not approve posts.", body = ApiMessage),
+        (status = 404, description = "No such comment.", body = ApiMessage),
+    ),
+)]
+async fn delete_comment_api(auth_user: AuthenticatedContributor, db: web::Data<Database>, pool: web::Data<crate::DbPool>, path: web::Path<(String, String)>) -> impl Responder {
+    let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
+    if !user.can_approve_posts {
+        return HttpResponse::Forbidden().json(json!({"success": false, "error": "Permission denied."}));
+    }
+    let comment_id = path.into_inner().1;
+    match comment_helpers::delete_comment(&db, &comment_id) {
+        Ok(1) => HttpResponse::Ok().json(json!({"success": true, "message": "Comment deleted."})),
+        Ok(removed) => HttpResponse::Ok().json(json!({"success": true, "message": format!("Comment and {} replies deleted.", removed - 1)})),
+        Err(e) => comment_action_error(&comment_id, e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/pending/{post_id}",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        ("post_id" = String, Path, description = "Post id."),
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "A post awaiting approval.", body = ApiResponse<FullPost>),
+        (status = 403, description = "Not allowed for this contributor.", body = ApiMessage),
+        (status = 404, description = "Not found.", body = ApiMessage),
+    ),
+)]
 async fn get_pending_post_details_api( auth_user: AuthenticatedContributor, pool: web::Data<crate::DbPool>, db: web::Data<Database>, path: web::Path<(String, String)>) -> impl Responder {
     let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
     if !user.can_approve_posts {
@@ -487,6 +805,22 @@ async fn get_pending_post_details_api( auth_user: AuthenticatedContributor, pool
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/management/{prefix}/api/pending/{post_id}/approve",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        ("post_id" = String, Path, description = "Post id."),
+    ),
+    request_body = ApproveRequest,
+    security(("session_cookie" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "The post was published.", body = ApiMessage),
+        (status = 400, description = "Invalid request.", body = ApiMessage),
+        (status = 403, description = "Not allowed for this contributor.", body = ApiMessage),
+    ),
+)]
 async fn approve_post_api( auth_user: AuthenticatedContributor, db: web::Data<Database>, pool: web::Data<crate::DbPool>, path: web::Path<(String, String)>, payload: web::Json<ApproveRequest> ) -> impl Responder {
     let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
     if !user.can_approve_posts {
@@ -505,6 +839,20 @@ async fn approve_post_api( auth_user: AuthenticatedContributor, db: web::Data<Da
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/management/{prefix}/api/pending/{post_id}/delete",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        ("post_id" = String, Path, description = "Post id."),
+    ),
+    security(("session_cookie" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "The pending post was deleted.", body = ApiMessage),
+        (status = 403, description = "Not allowed for this contributor.", body = ApiMessage),
+    ),
+)]
 async fn delete_pending_post_api(
     auth_user: AuthenticatedContributor,
     db: web::Data<Database>,
@@ -528,6 +876,19 @@ async fn delete_pending_post_api(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/mypending",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        PaginationQuery,
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "The contributor's own pending posts.", body = ApiResponse<PaginatedList<PostSummary>>),
+    ),
+)]
 async fn get_my_pending_posts_api( auth_user: AuthenticatedContributor, db: web::Data<Database>, pool: web::Data<crate::DbPool>, query: web::Query<PaginationQuery> ) -> impl Responder {
     let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
     let page = query.page.unwrap_or(1).max(1); // <-- FIX APPLIED
@@ -542,6 +903,20 @@ async fn get_my_pending_posts_api( auth_user: AuthenticatedContributor, db: web:
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/management/{prefix}/api/mypending/{post_id}/delete",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        ("post_id" = String, Path, description = "Post id."),
+    ),
+    security(("session_cookie" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "The pending post was deleted.", body = ApiMessage),
+        (status = 403, description = "Not allowed for this contributor.", body = ApiMessage),
+    ),
+)]
 async fn delete_my_pending_post_api(
     auth_user: AuthenticatedContributor,
     db: web::Data<Database>,
@@ -568,6 +943,20 @@ async fn delete_my_pending_post_api(
 // --- NEW APIs FOR EDITING ---
 
 /// NEW: API handler for a contributor to get the full details of their OWN PENDING post.
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/mypending/{post_id}",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        ("post_id" = String, Path, description = "Post id."),
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "One of the contributor's pending posts.", body = ApiResponse<FullPost>),
+        (status = 403, description = "Not allowed for this contributor.", body = ApiMessage),
+    ),
+)]
 async fn get_my_pending_post_details_api(auth_user: AuthenticatedContributor, pool: web::Data<crate::DbPool>, db: web::Data<Database>, path: web::Path<(String, String)>) -> impl Responder {
     let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
     let post_id = path.into_inner().1;
@@ -578,6 +967,20 @@ async fn get_my_pending_post_details_api(auth_user: AuthenticatedContributor, po
 }
 
 /// NEW: API handler for a contributor to get the full details of their OWN PUBLISHED post.
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/posts/{post_id}",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        ("post_id" = String, Path, description = "Post id."),
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "One of the contributor's published posts.", body = ApiResponse<FullPost>),
+        (status = 403, description = "Not allowed for this contributor.", body = ApiMessage),
+    ),
+)]
 async fn get_post_details_api(auth_user: AuthenticatedContributor, pool: web::Data<crate::DbPool>, db: web::Data<Database>, path: web::Path<(String, String)>) -> impl Responder {
     let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
     let post_id = path.into_inner().1;
@@ -588,6 +991,57 @@ async fn get_post_details_api(auth_user: AuthenticatedContributor, pool: web::Da
 }
 
 
+/// Daily views of a published post, for contributors who may edit it and for editors.
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/posts/{post_id}/stats",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        ("post_id" = String, Path, description = "Post id."),
+        StatsQuery,
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "View totals and daily views.", body = ApiResponse<PostViewStats>),
+        (status = 403, description = "Not allowed for this contributor.", body = ApiMessage),
+    ),
+)]
+async fn get_post_stats_api(
+    auth_user: AuthenticatedContributor,
+    pool: web::Data<crate::DbPool>,
+    db: web::Data<Database>,
+    path: web::Path<(String, String)>,
+    query: web::Query<StatsQuery>,
+) -> impl Responder {
+    let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
+    let post_id = path.into_inner().1;
+    let days = query.days.unwrap_or(30).clamp(1, 365);
+    match contributor_helpers::get_post_view_stats(&db, &pool, &user, &post_id, days) {
+        Ok(Some(stats)) => HttpResponse::Ok().json(ApiResponse { success: true, data: Some(stats), error: None }),
+        Ok(None) => HttpResponse::Forbidden().json(ApiResponse { success: false, data: None::<()>, error: Some("Post not found or permission denied.".to_string()) }),
+        Err(e) => {
+            log::error!("Failed to read view stats for post {}: {}", post_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse { success: false, data: None::<()>, error: Some("Failed to read view statistics.".to_string()) })
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/management/{prefix}/api/mypending/{post_id}/update",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+        ("post_id" = String, Path, description = "Post id."),
+    ),
+    request_body = FullPostUpdateRequest,
+    security(("session_cookie" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "The pending post was updated.", body = ApiMessage),
+        (status = 403, description = "Not allowed for this contributor.", body = ApiMessage),
+    ),
+)]
 async fn update_my_pending_post_api(
     auth_user: AuthenticatedContributor,
     path_params: web::Path<(String, String)>,
@@ -610,4 +1064,56 @@ async fn update_my_pending_post_api(
             HttpResponse::InternalServerError().json(json!({ "success": false, "error": format!("Database error during update: {}", e) }))
         }
     }
-}
\ No newline at end of file
+}
+
+// --- Public profile APIs ---
+
+#[utoipa::path(
+    get,
+    path = "/management/{prefix}/api/profile",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+    ),
+    security(("session_cookie" = [])),
+    responses(
+        (status = 200, description = "The contributor's public profile.", body = ApiResponse<ContributorProfile>),
+    ),
+)]
+async fn get_my_profile_api(auth_user: AuthenticatedContributor, pool: web::Data<crate::DbPool>) -> impl Responder {
+    let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
+    match contributor_helpers::get_own_profile(&pool, user.id) {
+        Ok(profile) => HttpResponse::Ok().json(ApiResponse { success: true, data: Some(profile), error: None }),
+        Err(e) => {
+            log::error!("Failed to load profile for user {}: {}", user.id, e);
+            HttpResponse::InternalServerError().json(ApiResponse { success: false, data: None::<()>, error: Some("Failed to load your profile.".to_string()) })
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/management/{prefix}/api/profile/update",
+    tag = "contributor",
+    params(
+        ("prefix" = String, Path, description = "Secret contributor path prefix."),
+    ),
+    request_body = ContributorProfile,
+    security(("session_cookie" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "The saved profile.", body = ApiResponse<ContributorProfile>),
+        (status = 400, description = "Invalid request.", body = ApiMessage),
+    ),
+)]
+async fn update_my_profile_api(
+    auth_user: AuthenticatedContributor,
+    db: web::Data<Database>,
+    pool: web::Data<crate::DbPool>,
+    payload: web::Json<ContributorProfile>,
+) -> impl Responder {
+    let user = match get_current_user(&auth_user, &pool) { Ok(u) => u, Err(resp) => return resp };
+    match contributor_helpers::update_own_profile(&db, &pool, &user, &payload) {
+        Ok(profile) => HttpResponse::Ok().json(json!({ "success": true, "message": "Profile saved.", "data": profile })),
+        Err(e) => HttpResponse::BadRequest().json(json!({ "success": false, "error": e.to_string() })),
+    }
+}
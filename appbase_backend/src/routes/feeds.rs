@@ -0,0 +1,115 @@
+use crate::config::Config;
+use crate::helper::feed_helpers::{self, FeedInfo};
+use crate::helper::render_helpers::RenderedHtmlCache;
+use actix_web::{web, HttpResponse, Responder};
+use redb::Database;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct FeedQuery {
+    // Overrides `site.feed_full_content` for this request.
+    full_content: Option<bool>,
+}
+
+#[derive(Clone, Copy)]
+enum FeedFormat {
+    Rss,
+    Atom,
+}
+
+pub fn config_feeds(cfg: &mut web::ServiceConfig) {
+    cfg.route("/feed.xml", web::get().to(site_rss_feed))
+        .route("/atom.xml", web::get().to(site_atom_feed))
+        // `{tag:.+}` so hierarchical tags like "rust/actix" work.
+        .route("/tags/{tag:.+}/feed.xml", web::get().to(tag_rss_feed))
+        .route("/tags/{tag:.+}/atom.xml", web::get().to(tag_atom_feed));
+}
+
+async fn site_rss_feed(
+    db: web::Data<Database>,
+    config: web::Data<Config>,
+    render_cache: web::Data<RenderedHtmlCache>,
+    query: web::Query<FeedQuery>,
+) -> impl Responder {
+    render_feed(&db, &config, &render_cache, None, query.full_content, FeedFormat::Rss)
+}
+
+async fn site_atom_feed(
+    db: web::Data<Database>,
+    config: web::Data<Config>,
+    render_cache: web::Data<RenderedHtmlCache>,
+    query: web::Query<FeedQuery>,
+) -> impl Responder {
+    render_feed(&db, &config, &render_cache, None, query.full_content, FeedFormat::Atom)
+}
+
+async fn tag_rss_feed(
+    tag: web::Path<String>,
+    db: web::Data<Database>,
+    config: web::Data<Config>,
+    render_cache: web::Data<RenderedHtmlCache>,
+    query: web::Query<FeedQuery>,
+) -> impl Responder {
+    render_feed(&db, &config, &render_cache, Some(tag.trim()), query.full_content, FeedFormat::Rss)
+}
+
+async fn tag_atom_feed(
+    tag: web::Path<String>,
+    db: web::Data<Database>,
+    config: web::Data<Config>,
+    render_cache: web::Data<RenderedHtmlCache>,
+    query: web::Query<FeedQuery>,
+) -> impl Responder {
+    render_feed(&db, &config, &render_cache, Some(tag.trim()), query.full_content, FeedFormat::Atom)
+}
+
+fn render_feed(
+    db: &Database,
+    config: &Config,
+    render_cache: &RenderedHtmlCache,
+    tag: Option<&str>,
+    full_content: Option<bool>,
+    format: FeedFormat,
+) -> HttpResponse {
+    let site = &config.site;
+    let full_content = full_content.unwrap_or(site.feed_full_content);
+
+    let entries = match feed_helpers::fetch_feed_entries(db, tag, site.feed_item_limit, full_content, render_cache) {
+        Ok(e) => e,
+        Err(e) => {
+            log::error!("Failed to build feed (tag: {:?}): {}", tag, e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let file_name = match format {
+        FeedFormat::Rss => "feed.xml",
+        FeedFormat::Atom => "atom.xml",
+    };
+    let info = match tag {
+        Some(t) => {
+            let encoded: String = url::form_urlencoded::byte_serialize(t.as_bytes()).collect();
+            FeedInfo {
+                title: format!("{} - {}", site.title, t),
+                description: format!("Posts tagged '{}' on {}", t, site.title),
+                link: site.tag_url(t),
+                self_url: site.absolute_url(&format!("/tags/{}/{}", encoded, file_name)),
+            }
+        }
+        None => FeedInfo {
+            title: site.title.clone(),
+            description: site.description.clone(),
+            link: site.absolute_url("/"),
+            self_url: site.absolute_url(&format!("/{}", file_name)),
+        },
+    };
+
+    match format {
+        FeedFormat::Rss => HttpResponse::Ok()
+            .content_type("application/rss+xml; charset=utf-8")
+            .body(feed_helpers::build_rss(site, &info, &entries)),
+        FeedFormat::Atom => HttpResponse::Ok()
+            .content_type("application/atom+xml; charset=utf-8")
+            .body(feed_helpers::build_atom(site, &info, &entries)),
+    }
+}
@@ -0,0 +1,400 @@
+use crate::config::Config;
+use crate::helper::public_helpers;
+use crate::models::{self, PaginatedList, PostSummary, TagTreeNode};
+use crate::DbPool;
+use actix_web::{web, HttpResponse, Responder};
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema, SimpleObject};
+use chrono::{DateTime, Utc};
+use redb::Database;
+use serde::Deserialize;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+// Guards against expensive queries from the public endpoint. List fields cost
+// `limit` times their selection, so nested lists add up quickly.
+const MAX_QUERY_DEPTH: usize = 8;
+const MAX_QUERY_COMPLEXITY: usize = 1000;
+const DEFAULT_PAGE_SIZE: u32 = 10;
+const MAX_PAGE_SIZE: u32 = 50;
+const DEFAULT_RELATED: u32 = 5;
+
+pub type PublicSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Per-request handles the resolvers read from.
+struct RequestData {
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+}
+
+fn request_data<'a>(ctx: &Context<'a>) -> &'a RequestData {
+    ctx.data_unchecked::<RequestData>()
+}
+
+/// Logs the real error and hands clients a generic one.
+fn internal_error(e: impl Display) -> async_graphql::Error {
+    log::error!("GraphQL resolver failed: {}", e);
+    async_graphql::Error::new("Internal server error.")
+}
+
+fn page_args(limit: Option<u32>, offset: Option<u32>) -> async_graphql::Result<(u32, u32)> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    if limit > MAX_PAGE_SIZE {
+        return Err(async_graphql::Error::new(format!("'limit' must not exceed {}.", MAX_PAGE_SIZE)));
+    }
+    Ok((limit, offset.unwrap_or(0)))
+}
+
+// ====================================================================
+// ============================== TYPES ===============================
+// ====================================================================
+
+/// A published post. The body is loaded only when `content` is selected.
+pub struct Post {
+    summary: PostSummary,
+    content: Option<String>,
+}
+
+impl From<PostSummary> for Post {
+    fn from(summary: PostSummary) -> Self {
+        Post { summary, content: None }
+    }
+}
+
+impl From<models::FullPost> for Post {
+    fn from(post: models::FullPost) -> Self {
+        Post {
+            summary: PostSummary { id: post.id, metadata: post.metadata, author: None },
+            content: Some(post.content),
+        }
+    }
+}
+
+#[Object]
+impl Post {
+    async fn id(&self) -> &str {
+        &self.summary.id
+    }
+
+    async fn title(&self) -> &str {
+        &self.summary.metadata.title
+    }
+
+    async fn summary(&self) -> &str {
+        &self.summary.metadata.summary
+    }
+
+    async fn slug(&self) -> Option<&str> {
+        self.summary.metadata.slug.as_deref()
+    }
+
+    async fn tags(&self) -> &[String] {
+        &self.summary.metadata.tags
+    }
+
+    async fn cover_image(&self) -> Option<&str> {
+        self.summary.metadata.cover_image.as_deref()
+    }
+
+    async fn has_call_to_action(&self) -> bool {
+        self.summary.metadata.has_call_to_action.unwrap_or(false)
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.summary.metadata.created_at
+    }
+
+    async fn last_updated_at(&self) -> Option<DateTime<Utc>> {
+        self.summary.metadata.last_updated_at
+    }
+
+    async fn word_count(&self) -> Option<u32> {
+        self.summary.metadata.word_count
+    }
+
+    async fn reading_time_minutes(&self) -> Option<u32> {
+        self.summary.metadata.reading_time_minutes
+    }
+
+    /// Heading outline; `anchor` is the heading's id in the rendered HTML.
+    async fn toc(&self) -> Vec<TocEntry> {
+        self.summary.metadata.toc.iter().cloned().map(TocEntry::from).collect()
+    }
+
+    async fn author(&self) -> Option<Author> {
+        self.summary.author.clone().map(Author::from)
+    }
+
+    /// Markdown body.
+    async fn content(&self, ctx: &Context<'_>) -> async_graphql::Result<String> {
+        if let Some(content) = &self.content {
+            return Ok(content.clone());
+        }
+        let data = request_data(ctx);
+        public_helpers::fetch_post_by_id(&self.summary.id, &data.db)
+            .map(|post| post.content)
+            .ok_or_else(|| async_graphql::Error::new("Post not found."))
+    }
+
+    /// Posts sharing tags or keywords with this one, best match first.
+    #[graphql(complexity = "limit.unwrap_or(DEFAULT_RELATED) as usize * child_complexity")]
+    async fn related(&self, ctx: &Context<'_>, limit: Option<u32>) -> async_graphql::Result<Vec<Post>> {
+        let limit = limit.unwrap_or(DEFAULT_RELATED).min(MAX_PAGE_SIZE);
+        let data = request_data(ctx);
+        let mut posts = public_helpers::fetch_related_posts(&self.summary.id, &data.db, limit)
+            .map_err(internal_error)?
+            .unwrap_or_default();
+        if ctx.look_ahead().field("author").exists() {
+            public_helpers::attach_authors(&data.pool, &data.config, &mut posts);
+        }
+        Ok(posts.into_iter().map(Post::from).collect())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Author {
+    username: String,
+    display_name: String,
+    avatar_url: Option<String>,
+}
+
+impl From<models::AuthorInfo> for Author {
+    fn from(author: models::AuthorInfo) -> Self {
+        Author { username: author.username, display_name: author.display_name, avatar_url: author.avatar_url }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct TocEntry {
+    level: u8,
+    text: String,
+    anchor: String,
+}
+
+impl From<models::TocEntry> for TocEntry {
+    fn from(entry: models::TocEntry) -> Self {
+        TocEntry { level: entry.level, text: entry.text, anchor: entry.anchor }
+    }
+}
+
+/// One page of posts; mirrors the REST `PaginatedList`.
+#[derive(SimpleObject)]
+pub struct PostPage {
+    items: Vec<Post>,
+    total: u64,
+    limit: u32,
+    offset: u32,
+    has_more: bool,
+}
+
+#[derive(SimpleObject)]
+pub struct TagNode {
+    name: String,
+    path: String,
+    post_count: u64,
+    children: Vec<TagNode>,
+}
+
+impl From<TagTreeNode> for TagNode {
+    fn from(node: TagTreeNode) -> Self {
+        TagNode {
+            name: node.name,
+            path: node.path,
+            post_count: node.post_count,
+            children: node.children.into_iter().map(TagNode::from).collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ArchiveMonth {
+    year: i32,
+    month: u32,
+    post_count: u64,
+}
+
+/// Turns a REST page into a `PostPage`, filling in bylines only if the query asks for them.
+fn to_post_page(ctx: &Context<'_>, mut page: PaginatedList<PostSummary>) -> PostPage {
+    if ctx.look_ahead().field("items").field("author").exists() {
+        let data = request_data(ctx);
+        public_helpers::attach_authors(&data.pool, &data.config, &mut page.items);
+    }
+    PostPage {
+        items: page.items.into_iter().map(Post::from).collect(),
+        total: page.total,
+        limit: page.limit,
+        offset: page.offset.unwrap_or(0),
+        has_more: page.has_more,
+    }
+}
+
+// ====================================================================
+// =============================== ROOT ===============================
+// ====================================================================
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn post(&self, ctx: &Context<'_>, id: String) -> Option<Post> {
+        let data = request_data(ctx);
+        let mut post: Post = public_helpers::fetch_post_by_id(&id, &data.db)?.into();
+        if ctx.look_ahead().field("author").exists() {
+            public_helpers::attach_authors(&data.pool, &data.config, std::slice::from_mut(&mut post.summary));
+        }
+        Some(post)
+    }
+
+    /// Looks a post up by its current or any former slug.
+    async fn post_by_slug(&self, ctx: &Context<'_>, slug: String) -> async_graphql::Result<Option<Post>> {
+        let data = request_data(ctx);
+        match public_helpers::resolve_post_slug(&slug, &data.db).map_err(internal_error)? {
+            Some((id, _current_slug)) => self.post(ctx, id).await,
+            None => Ok(None),
+        }
+    }
+
+    #[graphql(complexity = "limit.unwrap_or(DEFAULT_PAGE_SIZE) as usize * child_complexity")]
+    async fn latest_posts(&self, ctx: &Context<'_>, limit: Option<u32>, offset: Option<u32>) -> async_graphql::Result<PostPage> {
+        let (limit, offset) = page_args(limit, offset)?;
+        let page = public_helpers::fetch_latest_posts(&request_data(ctx).db, limit, offset).map_err(internal_error)?;
+        Ok(to_post_page(ctx, page))
+    }
+
+    #[graphql(complexity = "limit.unwrap_or(DEFAULT_PAGE_SIZE) as usize * child_complexity")]
+    async fn posts_by_tag(
+        &self,
+        ctx: &Context<'_>,
+        tag: String,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> async_graphql::Result<PostPage> {
+        let (limit, offset) = page_args(limit, offset)?;
+        let page = public_helpers::fetch_posts_by_tag(&tag, &request_data(ctx).db, limit, offset).map_err(internal_error)?;
+        Ok(to_post_page(ctx, page))
+    }
+
+    /// Posts published in a year, or in one month of it (UTC).
+    #[graphql(complexity = "limit.unwrap_or(DEFAULT_PAGE_SIZE) as usize * child_complexity")]
+    async fn archive_posts(
+        &self,
+        ctx: &Context<'_>,
+        year: i32,
+        month: Option<u32>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> async_graphql::Result<PostPage> {
+        let (limit, offset) = page_args(limit, offset)?;
+        match public_helpers::fetch_archive_posts(&request_data(ctx).db, year, month, limit, offset).map_err(internal_error)? {
+            Some(page) => Ok(to_post_page(ctx, page)),
+            None => Err(async_graphql::Error::new("Invalid year or month.")),
+        }
+    }
+
+    /// Published post counts per month, newest first.
+    async fn archive(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<ArchiveMonth>> {
+        let months = public_helpers::fetch_archive_month_counts(&request_data(ctx).db).map_err(internal_error)?;
+        Ok(months
+            .into_iter()
+            .map(|m| ArchiveMonth { year: m.year, month: m.month, post_count: m.post_count })
+            .collect())
+    }
+
+    /// The tag vocabulary, sorted.
+    async fn tags(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<String>> {
+        let mut tags = public_helpers::fetch_all_available_tags(&request_data(ctx).db).map_err(internal_error)?;
+        tags.sort_unstable();
+        Ok(tags)
+    }
+
+    /// Hierarchical tags with post counts.
+    async fn tag_tree(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<TagNode>> {
+        let tree = public_helpers::fetch_tag_tree(&request_data(ctx).db).map_err(internal_error)?;
+        Ok(tree.into_iter().map(TagNode::from).collect())
+    }
+}
+
+fn schema() -> &'static PublicSchema {
+    static SCHEMA: OnceLock<PublicSchema> = OnceLock::new();
+    SCHEMA.get_or_init(|| {
+        Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .limit_depth(MAX_QUERY_DEPTH)
+            .limit_complexity(MAX_QUERY_COMPLEXITY)
+            .finish()
+    })
+}
+
+// ====================================================================
+// ============================= HANDLERS =============================
+// ====================================================================
+
+#[derive(Deserialize)]
+pub struct GraphQLGetQuery {
+    query: String,
+    #[serde(rename = "operationName")]
+    operation_name: Option<String>,
+    // JSON-encoded variables object.
+    variables: Option<String>,
+}
+
+async fn execute(
+    request: async_graphql::Request,
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+) -> HttpResponse {
+    let request = request.data(RequestData { db, pool, config });
+    HttpResponse::Ok().json(schema().execute(request).await)
+}
+
+/// POST /api/graphql with a standard `{"query", "variables", "operationName"}` body.
+#[utoipa::path(
+    post,
+    path = "/api/graphql",
+    tag = "graphql",
+    request_body(content = Object, description = "GraphQL request: `query`, optional `variables` and `operationName`."),
+    responses((status = 200, description = "GraphQL response with `data` and/or `errors`.", body = Object)),
+)]
+pub async fn graphql_post(
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    body: web::Json<async_graphql::Request>,
+) -> impl Responder {
+    execute(body.into_inner(), db, pool, config).await
+}
+
+/// GET /api/graphql?query=...; the schema is read-only, so GET is as safe as POST.
+#[utoipa::path(
+    get,
+    path = "/api/graphql",
+    tag = "graphql",
+    params(
+        ("query" = String, Query, description = "GraphQL query document."),
+        ("operationName" = Option<String>, Query, description = "Operation to run if the document has several."),
+        ("variables" = Option<String>, Query, description = "JSON-encoded variables object."),
+    ),
+    responses(
+        (status = 200, description = "GraphQL response with `data` and/or `errors`.", body = Object),
+        (status = 400, description = "`variables` is not valid JSON.", body = String),
+    ),
+)]
+pub async fn graphql_get(
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    query: web::Query<GraphQLGetQuery>,
+) -> impl Responder {
+    let query = query.into_inner();
+    let mut request = async_graphql::Request::new(query.query);
+    if let Some(name) = query.operation_name {
+        request = request.operation_name(name);
+    }
+    if let Some(raw) = query.variables.filter(|v| !v.trim().is_empty()) {
+        match serde_json::from_str(&raw) {
+            Ok(variables) => request = request.variables(async_graphql::Variables::from_json(variables)),
+            Err(_) => return HttpResponse::BadRequest().json("Invalid 'variables' query parameter."),
+        }
+    }
+    execute(request, db, pool, config).await
+}
@@ -2,4 +2,8 @@
 pub mod admin;
 pub mod contributor;
 pub mod public;
-pub mod advanced_db_manager;
\ No newline at end of file
+pub mod advanced_db_manager;
+pub mod feeds;
+pub mod sitemap;
+pub mod openapi;
+pub mod graphql;
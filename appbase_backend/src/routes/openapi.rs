@@ -0,0 +1,56 @@
+use crate::routes::{contributor::ContributorApiDoc, public::PublicApiDoc};
+use actix_web::{HttpResponse, Responder};
+use std::sync::OnceLock;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+use utoipa::OpenApi;
+
+/// Top-level document; the route modules contribute their own paths and schemas.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "AppBase API",
+        description = "Public read API and the contributor dashboard API. Generated from the route handlers and models.",
+    ),
+)]
+struct ApiDoc;
+
+/// Builds the merged specification, including the security schemes used by the contributor API.
+pub fn build_openapi() -> utoipa::openapi::OpenApi {
+    let mut doc = ApiDoc::openapi();
+    doc.merge(PublicApiDoc::openapi());
+    doc.merge(ContributorApiDoc::openapi());
+
+    let components = doc.components.get_or_insert_with(Default::default);
+    components.add_security_scheme(
+        "session_cookie",
+        SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::with_description(
+            "id",
+            "Session cookie set by the contributor login form.",
+        ))),
+    );
+    components.add_security_scheme(
+        "csrf_token",
+        SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+            "X-CSRF-Token",
+            "CSRF token embedded in the contributor dashboard page.",
+        ))),
+    );
+    doc
+}
+
+/// GET /api/openapi.json
+pub async fn get_openapi_spec() -> impl Responder {
+    // The spec only depends on the code, so it is built once.
+    static SPEC: OnceLock<String> = OnceLock::new();
+    let spec = SPEC.get_or_init(|| {
+        build_openapi().to_pretty_json().unwrap_or_else(|e| {
+            log::error!("Failed to serialise the OpenAPI spec: {}", e);
+            String::new()
+        })
+    });
+
+    if spec.is_empty() {
+        return HttpResponse::InternalServerError().finish();
+    }
+    HttpResponse::Ok().content_type("application/json").body(spec.clone())
+}
@@ -1,8 +1,25 @@
 
+use crate::config::Config;
+use crate::helper::comment_helpers::{self, CommentSubmission, SubmissionOutcome};
+use crate::helper::conditional_get_helpers::Validators;
+use crate::helper::fieldset_helpers::{self, FieldSelection, FieldsQuery};
 use crate::helper::public_helpers;
-use actix_web::{web, HttpResponse, Responder};
+use crate::helper::reaction_helpers::{self, ReactionOutcome};
+use crate::helper::render_helpers::RenderedHtmlCache;
+use crate::helper::view_helpers::{self, ViewCounter};
+use crate::models::db_operations::posts_db_operations::PostCursor;
+use crate::models::{ArchiveMonth, AuthorInfo, AuthorProfile, CommentThread, FullPost, PaginatedList, PostMetadata, PostOutline, PopularPost, PostSummary, ProfileLink, ReactionResult, ReactionToken, Suggestions, TagTreeNode, TermSuggestion, TitleSuggestion, TocEntry};
+use crate::{AppState, DbPool};
+use crate::routes::{graphql, openapi};
+use crate::middleware::rate_limit::{RateLimit, RateLimitScope};
+use crate::models::db_operations::fuzzy_index_operations::{self, FuzzySources};
+use crate::models::db_operations::suggest_operations::SuggestKinds;
+use crate::models::db_operations::tag_filter_operations::{self, TagExpr};
+use actix_web::http::header::{self, Accept, Header};
+use actix_web::{guard, web, HttpRequest, HttpResponse, Responder};
 use redb::Database;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{OpenApi, ToSchema};
 
 
 fn deserialize_tags<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
@@ -33,12 +50,82 @@ pub struct ApiQuery {
     limit: Option<u32>,
     offset: Option<u32>,
     q: Option<String>,
+    // Opaque keyset cursor. An empty value requests the first page.
+    cursor: Option<String>,
+    // Search mode: "keyword" (default) or "fulltext".
+    mode: Option<String>,
+    // Keyword mode only: also match tags, keywords and titles within a small edit distance.
+    fuzzy: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct SuggestQuery {
+    q: Option<String>,
+    // Completions per group.
+    limit: Option<u32>,
+    // Comma-separated subset of "tags", "keywords", "titles", "vocabulary".
+    // Defaults to the first three.
+    types: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct PopularQuery {
+    // Number of days ending today, e.g. "7d".
+    window: Option<String>,
+    limit: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct CommentsQuery {
+    // Top-level comments per page; replies are always included.
+    limit: Option<u32>,
+    offset: Option<u32>,
+}
+
+/// A reader comment. `content` is Markdown; HTML is escaped.
+#[derive(Deserialize, ToSchema)]
+pub struct CommentRequest {
+    author_name: String,
+    content: String,
+    /// Id of the approved comment this replies to.
+    parent_id: Option<String>,
+    /// Honeypot. Keep this input hidden in the comment form and leave it empty.
+    website: Option<String>,
+}
+
+/// A reaction, authorised by a token from `/api/reactions/token`.
+#[derive(Deserialize, ToSchema)]
+pub struct ReactionRequest {
+    token: String,
+}
+
+#[derive(Deserialize)]
+pub struct PostIdsQuery {
+    // Comma-separated post ids, e.g. "a,b,c".
+    ids: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct PostFormatQuery {
+    // "json" | "html" | "markdown"; overrides the Accept header.
+    format: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct TagFilterQuery {
-    #[serde(deserialize_with = "deserialize_tags")]
+    // Posts must carry every one of these tags. `tags` is the original name of `all`.
+    #[serde(default, deserialize_with = "deserialize_tags")]
     tags: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_tags")]
+    all: Vec<String>,
+    // Posts must carry at least one of these tags.
+    #[serde(default, deserialize_with = "deserialize_tags")]
+    any: Vec<String>,
+    // Posts must carry none of these tags.
+    #[serde(default, deserialize_with = "deserialize_tags")]
+    exclude: Vec<String>,
+    // Boolean expression, e.g. "rust AND (actix OR axum) NOT draft". ANDed with the sets above.
+    expr: Option<String>,
     // **PAGINATION PARAMETERS ARE CORRECTLY INCLUDED HERE**
     limit: Option<u32>,
     offset: Option<u32>,
@@ -47,33 +134,823 @@ pub struct TagFilterQuery {
 pub fn config_api(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api")
+            .wrap(RateLimit(RateLimitScope::PublicApi))
+            .route("/openapi.json", web::get().to(openapi::get_openapi_spec))
             .route("/is_server_active", web::get().to(is_server_active))
+            .route("/posts", web::get().to(get_posts_by_ids))
             .route("/posts/latest", web::get().to(get_latest_posts))
-            .route("/posts/search", web::get().to(search_posts_by_keyword))
+            .route("/posts/popular", web::get().to(get_popular_posts))
+            .service(
+                web::resource("/posts/search")
+                    .guard(guard::Get())
+                    .wrap(RateLimit(RateLimitScope::Search))
+                    .to(search_posts_by_keyword),
+            )
             .route("/posts/tag/{tag}", web::get().to(get_posts_by_tag))
             .route("/posts/filter", web::get().to(filter_posts_by_tags))
+            .route("/posts/slug/{slug}", web::get().to(get_post_by_slug))
+            .route("/posts/archive", web::get().to(get_archive_counts))
+            .route("/posts/archive/{year}", web::get().to(get_archive_year))
+            .route("/posts/archive/{year}/{month}", web::get().to(get_archive_month))
+            .route("/posts/{id}/related", web::get().to(get_related_posts))
+            .route("/posts/{id}/toc", web::get().to(get_post_toc))
+            .route("/posts/{id}/view", web::post().to(record_post_view))
+            .route("/posts/{id}/comments", web::get().to(get_post_comments))
+            .service(
+                web::resource("/posts/{id}/comments")
+                    .guard(guard::Post())
+                    .wrap(RateLimit(RateLimitScope::Comments))
+                    .to(submit_post_comment),
+            )
+            .route("/posts/{id}/reactions", web::get().to(get_post_reactions))
+            .route("/posts/{id}/reactions/{kind}", web::post().to(add_post_reaction))
+            .service(
+                web::resource("/reactions/token")
+                    .guard(guard::Get())
+                    .wrap(RateLimit(RateLimitScope::ReactionTokens))
+                    .to(get_reaction_token),
+            )
             .route("/posts/{id}", web::get().to(get_post_by_id))
-            .route("/tags/available", web::get().to(get_available_tags)),
+            .route("/authors", web::get().to(get_authors))
+            .route("/authors/{username}", web::get().to(get_author))
+            .route("/authors/{username}/posts", web::get().to(get_author_posts))
+            .route("/tags/available", web::get().to(get_available_tags))
+            .route("/tags/tree", web::get().to(get_tag_tree))
+            .route("/suggest", web::get().to(get_suggestions))
+            .route("/graphql", web::post().to(graphql::graphql_post))
+            .route("/graphql", web::get().to(graphql::graphql_get)),
     );
 }
 
+/// OpenAPI description of every route in `config_api`; served as part of `/api/openapi.json`.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        is_server_active,
+        get_posts_by_ids,
+        get_latest_posts,
+        get_popular_posts,
+        search_posts_by_keyword,
+        get_posts_by_tag,
+        filter_posts_by_tags,
+        get_post_by_slug,
+        get_archive_counts,
+        get_archive_year,
+        get_archive_month,
+        get_related_posts,
+        get_post_toc,
+        record_post_view,
+        get_post_comments,
+        submit_post_comment,
+        get_post_reactions,
+        add_post_reaction,
+        get_reaction_token,
+        get_post_by_id,
+        get_authors,
+        get_author,
+        get_author_posts,
+        get_available_tags,
+        get_tag_tree,
+        get_suggestions,
+        graphql::graphql_post,
+        graphql::graphql_get,
+    ),
+    components(schemas(PostSummary, FullPost, PostMetadata, AuthorInfo, AuthorProfile, ProfileLink, ArchiveMonth, TagTreeNode, PostOutline, TocEntry, Suggestions, TermSuggestion, TitleSuggestion, PopularPost, CommentThread, CommentRequest, ReactionRequest, ReactionResult, ReactionToken)),
+    tags(
+        (name = "posts", description = "Published posts."),
+        (name = "archive", description = "Posts by publication month."),
+        (name = "authors", description = "Public contributor profiles."),
+        (name = "tags", description = "Tag vocabulary and hierarchy."),
+        (name = "comments", description = "Moderated reader comments."),
+        (name = "reactions", description = "Anonymous reactions to posts."),
+        (name = "status", description = "Health check."),
+        (name = "graphql", description = "Read-only GraphQL view of posts, tags and archives."),
+    ),
+)]
+pub struct PublicApiDoc;
+
+// Upper bound for /api/posts?ids=.
+const MAX_BATCH_IDS: usize = 100;
+
+// Limits for /api/suggest.
+const MAX_SUGGEST_PREFIX_CHARS: usize = 100;
+const DEFAULT_SUGGEST_LIMIT: u32 = 5;
+const MAX_SUGGEST_LIMIT: u32 = 20;
+
+// Page size limits for /api/posts/{id}/comments.
+const DEFAULT_COMMENTS_LIMIT: u32 = 20;
+const MAX_COMMENTS_LIMIT: u32 = 100;
+
+// Limits for /api/posts/popular.
+const DEFAULT_POPULAR_WINDOW_DAYS: u32 = 7;
+const MAX_POPULAR_WINDOW_DAYS: u32 = 365;
+const DEFAULT_POPULAR_LIMIT: u32 = 10;
+const MAX_POPULAR_LIMIT: u32 = 50;
+
+/// Decodes the `cursor` query parameter. An empty string means "first page".
+fn parse_cursor(raw: &str) -> Result<Option<PostCursor>, HttpResponse> {
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    PostCursor::decode(raw)
+        .map(Some)
+        .ok_or_else(|| HttpResponse::BadRequest().json("Invalid 'cursor' query parameter."))
+}
+
+/// Loads the list validators and answers `304 Not Modified` right away when the
+/// client's copy is current. `Err` carries the response to return.
+fn check_list_validators(req: &HttpRequest, db: &Database) -> Result<Validators, HttpResponse> {
+    let validators = Validators::for_published_lists(db).map_err(|e| {
+        log::error!("Failed to read publish state: {}", e);
+        HttpResponse::InternalServerError().finish()
+    })?;
+    if validators.is_not_modified(req) {
+        return Err(validators.not_modified_response());
+    }
+    Ok(validators)
+}
+
+/// `200 OK` JSON response carrying `ETag` / `Last-Modified`.
+fn json_with_validators<T: Serialize>(validators: &Validators, body: T) -> HttpResponse {
+    let mut builder = HttpResponse::Ok();
+    validators.apply(&mut builder);
+    builder.json(body)
+}
+
+/// `json_with_validators` for post summaries (a list or a `PaginatedList`), trimmed
+/// by `?fields=` and, with `?include=content`, carrying each post's markdown body.
+fn shaped_posts_with_validators<T: Serialize>(
+    req: &HttpRequest,
+    validators: &Validators,
+    db: &web::Data<Database>,
+    body: &T,
+) -> HttpResponse {
+    let selection = match FieldSelection::from_request(req) {
+        Ok(s) => s,
+        Err(message) => return HttpResponse::BadRequest().json(message),
+    };
+    if selection.is_default() {
+        return json_with_validators(validators, body);
+    }
+
+    let load_content = |id: &str| public_helpers::fetch_post_by_id(id, db).map(|post| post.content);
+    match fieldset_helpers::shape_post_list(body, &selection, load_content) {
+        Ok(shaped) => json_with_validators(validators, shaped),
+        Err(e) => {
+            log::error!("Failed to apply field selection: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// `shaped_posts_with_validators` for a page of post summaries, with author bylines filled in.
+fn posts_with_validators(
+    req: &HttpRequest,
+    validators: &Validators,
+    db: &web::Data<Database>,
+    pool: &web::Data<DbPool>,
+    config: &Config,
+    mut page: PaginatedList<PostSummary>,
+) -> HttpResponse {
+    public_helpers::attach_authors(pool, config, &mut page.items);
+    shaped_posts_with_validators(req, validators, db, &page)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/is_server_active",
+    tag = "status",
+    responses((status = 200, description = "Always the plain text `active`.", body = String, content_type = "text/plain")),
+)]
 async fn is_server_active() -> impl Responder {
     HttpResponse::Ok().body("active")
 }
 
-async fn get_post_by_id(id: web::Path<String>, db: web::Data<Database>) -> impl Responder {
-    match public_helpers::fetch_post_by_id(&id, &db) {
-        Some(post) => HttpResponse::Ok().json(post),
+/// The representations `/api/posts/{id}` can be served in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PostFormat {
+    Json,
+    Html,
+    Markdown,
+}
+
+/// Picks the post representation: `?format=` wins, otherwise the highest ranked
+/// supported type in `Accept`. Anything unsupported falls back to JSON.
+fn negotiate_post_format(req: &HttpRequest, format_param: Option<&str>) -> Result<PostFormat, HttpResponse> {
+    match format_param {
+        Some("json") => return Ok(PostFormat::Json),
+        Some("html") => return Ok(PostFormat::Html),
+        Some("markdown") | Some("md") => return Ok(PostFormat::Markdown),
+        Some(_) => return Err(HttpResponse::BadRequest().json("Invalid 'format' query parameter. Use 'json', 'html' or 'markdown'.")),
+        None => {}
+    }
+
+    if let Ok(accept) = Accept::parse(req) {
+        for mime in accept.ranked() {
+            match (mime.type_().as_str(), mime.subtype().as_str()) {
+                ("application", "json") | ("*", "*") | ("application", "*") => return Ok(PostFormat::Json),
+                ("text", "html") => return Ok(PostFormat::Html),
+                ("text", "markdown") => return Ok(PostFormat::Markdown),
+                _ => continue,
+            }
+        }
+    }
+    Ok(PostFormat::Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}",
+    tag = "posts",
+    params(
+        ("id" = String, Path, description = "Post id."),
+        ("format" = Option<String>, Query, description = "`json`, `html` or `markdown`; overrides `Accept`."),
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "The post, as JSON, sanitised HTML or markdown.", body = FullPost),
+        (status = 304, description = "The client's copy is current."),
+        (status = 400, description = "Invalid format or field selection.", body = String),
+        (status = 404, description = "Unknown post."),
+    ),
+)]
+async fn get_post_by_id(
+    req: HttpRequest,
+    id: web::Path<String>,
+    db: web::Data<Database>,
+    render_cache: web::Data<RenderedHtmlCache>,
+    config: web::Data<Config>,
+    view_counter: web::Data<ViewCounter>,
+    query: web::Query<PostFormatQuery>,
+) -> impl Responder {
+    let resp = respond_with_post(&req, &id, &db, &render_cache, query.format.as_deref());
+    count_read(&req, &id, &resp, &config, &view_counter);
+    resp
+}
+
+/// Counts a successful post read as a view, unless reads don't count (see `ViewCounterConfig`).
+fn count_read(req: &HttpRequest, id: &str, resp: &HttpResponse, config: &Config, view_counter: &ViewCounter) {
+    let served = resp.status().is_success() || resp.status() == actix_web::http::StatusCode::NOT_MODIFIED;
+    if served
+        && config.view_counter.enabled
+        && config.view_counter.count_on_read
+        && view_helpers::is_countable_request(req)
+    {
+        view_counter.record(id);
+    }
+}
+
+/// Looks a post up by slug. Slugs from before a title change answer with a
+/// `301` to the current slug, so shared links keep working.
+#[utoipa::path(
+    get,
+    path = "/api/posts/slug/{slug}",
+    tag = "posts",
+    params(
+        ("slug" = String, Path, description = "Current or former slug of the post."),
+        ("format" = Option<String>, Query, description = "`json`, `html` or `markdown`; overrides `Accept`."),
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "The post.", body = FullPost),
+        (status = 301, description = "Former slug; `Location` points to the current one."),
+        (status = 304, description = "The client's copy is current."),
+        (status = 404, description = "Unknown slug."),
+    ),
+)]
+async fn get_post_by_slug(
+    req: HttpRequest,
+    slug: web::Path<String>,
+    db: web::Data<Database>,
+    render_cache: web::Data<RenderedHtmlCache>,
+    config: web::Data<Config>,
+    view_counter: web::Data<ViewCounter>,
+    query: web::Query<PostFormatQuery>,
+) -> impl Responder {
+    let requested = slug.into_inner();
+    let (post_id, current_slug) = match public_helpers::resolve_post_slug(&requested, &db) {
+        Ok(Some(found)) => found,
+        Ok(None) => return HttpResponse::NotFound().body("Post not found"),
+        Err(e) => {
+            log::error!("Failed to resolve slug '{}': {}", requested, e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    if let Some(current) = current_slug.filter(|current| *current != requested.to_lowercase()) {
+        let encoded: String = url::form_urlencoded::byte_serialize(current.as_bytes()).collect();
+        let mut location = format!("/api/posts/slug/{}", encoded);
+        if !req.query_string().is_empty() {
+            location.push('?');
+            location.push_str(req.query_string());
+        }
+        return HttpResponse::MovedPermanently()
+            .insert_header((header::LOCATION, location))
+            .finish();
+    }
+
+    let resp = respond_with_post(&req, &post_id, &db, &render_cache, query.format.as_deref());
+    count_read(&req, &post_id, &resp, &config, &view_counter);
+    resp
+}
+
+/// Serves a published post in the negotiated format, honouring conditional GET.
+/// JSON responses carry the post's reaction counts when reactions are enabled.
+fn respond_with_post(
+    req: &HttpRequest,
+    id: &str,
+    db: &web::Data<Database>,
+    render_cache: &RenderedHtmlCache,
+    format_param: Option<&str>,
+) -> HttpResponse {
+    let format = match negotiate_post_format(req, format_param) {
+        Ok(f) => f,
+        Err(resp) => return resp,
+    };
+    let selection = match FieldSelection::from_request(req) {
+        Ok(s) => s,
+        Err(message) => return HttpResponse::BadRequest().json(message),
+    };
+
+    let mut post = match public_helpers::fetch_post_by_id(id, db) {
+        Some(post) => post,
+        None => return HttpResponse::NotFound().body("Post not found"),
+    };
+
+    let post_validators = Validators::for_post(&post);
+    let reaction_kinds = req.app_data::<web::Data<AppState>>().map(|state| reaction_kinds(state)).unwrap_or_default();
+    if matches!(format, PostFormat::Json) && !reaction_kinds.is_empty() && selection.wants("reactions") {
+        match reaction_helpers::fetch_reaction_counts(db, &post.id, &reaction_kinds) {
+            Ok(counts) => post.reactions = Some(counts),
+            Err(e) => {
+                log::error!("Failed to read reactions for post {}: {}", id, e);
+                return HttpResponse::InternalServerError().finish();
+            }
+        }
+    }
+    let validators = match format {
+        PostFormat::Json => match &post.reactions {
+            Some(reactions) => post_validators.with_counters(reactions),
+            None => post_validators,
+        },
+        PostFormat::Html => post_validators.for_representation("html"),
+        PostFormat::Markdown => post_validators.for_representation("md"),
+    };
+    if validators.is_not_modified(req) {
+        let mut resp = validators.not_modified_response();
+        resp.headers_mut().insert(header::VARY, header::HeaderValue::from_static("Accept"));
+        return resp;
+    }
+
+    let mut builder = HttpResponse::Ok();
+    validators.apply(&mut builder);
+    builder.insert_header((header::VARY, "Accept"));
+
+    match format {
+        PostFormat::Json if selection.is_default() => builder.json(post),
+        PostFormat::Json => match fieldset_helpers::shape_post(&post, &selection) {
+            Ok(shaped) => builder.json(shaped),
+            Err(e) => {
+                log::error!("Failed to apply field selection to post {}: {}", id, e);
+                HttpResponse::InternalServerError().finish()
+            }
+        },
+        PostFormat::Html => {
+            let html = render_cache.get_or_render(&post);
+            builder.content_type("text/html; charset=utf-8").body(html.as_str().to_owned())
+        }
+        PostFormat::Markdown => {
+            // Content is stored entity-escaped; serve the author's Markdown source.
+            let markdown = html_escape::decode_html_entities(&post.content).into_owned();
+            builder.content_type("text/markdown; charset=utf-8").body(markdown)
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/related",
+    tag = "posts",
+    params(
+        ("id" = String, Path, description = "Post id."),
+        ("limit" = Option<u32>, Query, description = "Maximum number of posts (default 5, at most 50)."),
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "Posts sharing tags or keywords, best match first.", body = Vec<PostSummary>),
+        (status = 304, description = "The client's copy is current."),
+        (status = 404, description = "Unknown post."),
+    ),
+)]
+async fn get_related_posts(
+    req: HttpRequest,
+    id: web::Path<String>,
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    query: web::Query<ApiQuery>,
+) -> impl Responder {
+    let limit = query.limit.unwrap_or(5).min(50);
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match public_helpers::fetch_related_posts(&id, &db, limit) {
+        Ok(Some(mut posts)) => {
+            public_helpers::attach_authors(&pool, &config, &mut posts);
+            shaped_posts_with_validators(&req, &validators, &db, &posts)
+        }
+        Ok(None) => HttpResponse::NotFound().body("Post not found"),
+        Err(e) => {
+            log::error!("Failed to fetch related posts for '{}': {}", id, e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// Reading time and table of contents of a post, without its body.
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/toc",
+    tag = "posts",
+    params(("id" = String, Path, description = "Post id.")),
+    responses(
+        (status = 200, description = "Word count, reading time and heading outline.", body = PostOutline),
+        (status = 304, description = "The client's copy is current."),
+        (status = 404, description = "Unknown post."),
+    ),
+)]
+async fn get_post_toc(req: HttpRequest, id: web::Path<String>, db: web::Data<Database>) -> impl Responder {
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match public_helpers::fetch_post_outline(&id, &db) {
+        Some(outline) => json_with_validators(&validators, outline),
         None => HttpResponse::NotFound().body("Post not found"),
     }
 }
 
-async fn get_latest_posts(db: web::Data<Database>, query: web::Query<ApiQuery>) -> impl Responder {
+/// View beacon for frontends that render posts from a cache or prefetch them, e.g.
+/// `navigator.sendBeacon("/api/posts/{id}/view")`. Bots are ignored; nothing about
+/// the client is stored.
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/view",
+    tag = "posts",
+    params(("id" = String, Path, description = "Post id.")),
+    responses(
+        (status = 204, description = "The view was counted, or ignored as a bot."),
+        (status = 404, description = "Unknown post."),
+    ),
+)]
+async fn record_post_view(
+    req: HttpRequest,
+    id: web::Path<String>,
+    db: web::Data<Database>,
+    config: web::Data<Config>,
+    view_counter: web::Data<ViewCounter>,
+) -> impl Responder {
+    if public_helpers::fetch_post_by_id(&id, &db).is_none() {
+        return HttpResponse::NotFound().body("Post not found");
+    }
+    if config.view_counter.enabled && view_helpers::is_countable_request(&req) {
+        view_counter.record(&id);
+    }
+    HttpResponse::NoContent().finish()
+}
+
+/// Approved comments of a post as threads: top-level comments oldest first, each with
+/// its replies nested under `replies`.
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/comments",
+    tag = "comments",
+    params(
+        ("id" = String, Path, description = "Post id."),
+        ("limit" = Option<u32>, Query, description = "Top-level comments per page (default 20, at most 100)."),
+        ("offset" = Option<u32>, Query, description = "Number of top-level comments to skip."),
+    ),
+    responses(
+        (status = 200, description = "One page of comment threads.", body = PaginatedList<CommentThread>),
+        (status = 404, description = "Unknown post."),
+    ),
+)]
+async fn get_post_comments(
+    id: web::Path<String>,
+    db: web::Data<Database>,
+    query: web::Query<CommentsQuery>,
+) -> impl Responder {
+    let limit = query.limit.unwrap_or(DEFAULT_COMMENTS_LIMIT).clamp(1, MAX_COMMENTS_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+
+    match comment_helpers::fetch_comment_threads(&db, &id, limit, offset) {
+        Ok(Some(page)) => HttpResponse::Ok().json(page),
+        Ok(None) => HttpResponse::NotFound().body("Post not found"),
+        Err(e) => {
+            log::error!("Failed to fetch comments for post '{}': {}", id, e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// Submits a comment for moderation. It is public once approved from the dashboard.
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/comments",
+    tag = "comments",
+    params(("id" = String, Path, description = "Post id.")),
+    request_body = CommentRequest,
+    responses(
+        (status = 202, description = "The comment awaits moderation.", body = String),
+        (status = 400, description = "Invalid comment, too many links or unknown parent.", body = String),
+        (status = 403, description = "Comments are closed.", body = String),
+        (status = 404, description = "Unknown post."),
+        (status = 429, description = "Too many comments from this address."),
+    ),
+)]
+async fn submit_post_comment(
+    id: web::Path<String>,
+    db: web::Data<Database>,
+    config: web::Data<Config>,
+    payload: web::Json<CommentRequest>,
+) -> impl Responder {
+    if !config.comments.enabled {
+        return HttpResponse::Forbidden().json("Comments are closed.");
+    }
+    let submission = CommentSubmission {
+        author_name: &payload.author_name,
+        content: &payload.content,
+        parent_id: payload.parent_id.as_deref(),
+        website: payload.website.as_deref(),
+    };
+
+    match comment_helpers::submit_comment(&db, &config.comments, &id, submission) {
+        Ok(SubmissionOutcome::Queued) | Ok(SubmissionOutcome::Discarded) => {
+            HttpResponse::Accepted().json("Thank you! Your comment will appear once it has been approved.")
+        }
+        Ok(SubmissionOutcome::Rejected(message)) => HttpResponse::BadRequest().json(message),
+        Ok(SubmissionOutcome::PostNotFound) => HttpResponse::NotFound().body("Post not found"),
+        Err(e) => {
+            log::error!("Failed to store comment on post '{}': {}", id, e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// The reaction kinds currently enabled in the site settings.
+fn reaction_kinds(app_state: &AppState) -> Vec<String> {
+    app_state.reaction_kinds.read().map(|kinds| kinds.clone()).unwrap_or_default()
+}
+
+/// Reaction counts of a post, one entry per enabled kind.
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/reactions",
+    tag = "reactions",
+    params(("id" = String, Path, description = "Post id.")),
+    responses(
+        (status = 200, description = "Reactions per kind.", body = std::collections::BTreeMap<String, u64>),
+        (status = 404, description = "Unknown post."),
+    ),
+)]
+async fn get_post_reactions(
+    id: web::Path<String>,
+    db: web::Data<Database>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let Some(post) = public_helpers::fetch_post_by_id(&id, &db) else {
+        return HttpResponse::NotFound().body("Post not found");
+    };
+    match reaction_helpers::fetch_reaction_counts(&db, &post.id, &reaction_kinds(&app_state)) {
+        Ok(reactions) => HttpResponse::Ok().json(reactions),
+        Err(e) => {
+            log::error!("Failed to read reactions for post '{}': {}", id, e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// Adds a reaction. Each token counts once per post and kind; repeating a reaction
+/// answers `counted: false` with the unchanged counts.
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/reactions/{kind}",
+    tag = "reactions",
+    params(
+        ("id" = String, Path, description = "Post id."),
+        ("kind" = String, Path, description = "One of the reaction kinds enabled by the admin."),
+    ),
+    request_body = ReactionRequest,
+    responses(
+        (status = 200, description = "The reaction and the post's updated counts.", body = ReactionResult),
+        (status = 400, description = "Unknown kind, or a missing, invalid or expired token.", body = String),
+        (status = 403, description = "Reactions are disabled.", body = String),
+        (status = 404, description = "Unknown post."),
+    ),
+)]
+async fn add_post_reaction(
+    path: web::Path<(String, String)>,
+    db: web::Data<Database>,
+    app_state: web::Data<AppState>,
+    payload: web::Json<ReactionRequest>,
+) -> impl Responder {
+    let (id, kind) = path.into_inner();
+    let kinds = reaction_kinds(&app_state);
+    if kinds.is_empty() {
+        return HttpResponse::Forbidden().json("Reactions are disabled.");
+    }
+
+    match reaction_helpers::react(&db, &app_state.reaction_token_key, &kinds, &id, &kind, &payload.token) {
+        Ok(ReactionOutcome::Reacted(result)) => HttpResponse::Ok().json(result),
+        Ok(ReactionOutcome::UnknownKind) => {
+            HttpResponse::BadRequest().json(format!("Unknown reaction '{}'. Supported: {}.", kind, kinds.join(", ")))
+        }
+        Ok(ReactionOutcome::InvalidToken) => {
+            HttpResponse::BadRequest().json("Missing, invalid or expired token. Fetch a new one from /api/reactions/token.")
+        }
+        Ok(ReactionOutcome::PostNotFound) => HttpResponse::NotFound().body("Post not found"),
+        Err(e) => {
+            log::error!("Failed to store reaction '{}' on post '{}': {}", kind, id, e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// Issues a reaction token. Clients keep it (e.g. in `localStorage`) and fetch a new
+/// one after `expires_at`. No account or cookie is involved.
+#[utoipa::path(
+    get,
+    path = "/api/reactions/token",
+    tag = "reactions",
+    responses(
+        (status = 200, description = "A signed token, valid until `expires_at`.", body = ReactionToken),
+        (status = 403, description = "Reactions are disabled.", body = String),
+        (status = 429, description = "Too many tokens requested from this address."),
+    ),
+)]
+async fn get_reaction_token(app_state: web::Data<AppState>) -> impl Responder {
+    if reaction_kinds(&app_state).is_empty() {
+        return HttpResponse::Forbidden().json("Reactions are disabled.");
+    }
+    HttpResponse::Ok()
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .json(reaction_helpers::issue_token(&app_state.reaction_token_key))
+}
+
+/// Parses a `window` such as `7d` into a number of days.
+fn parse_window_days(raw: Option<&str>) -> Result<u32, HttpResponse> {
+    let Some(raw) = raw else { return Ok(DEFAULT_POPULAR_WINDOW_DAYS) };
+    raw.strip_suffix('d')
+        .and_then(|days| days.parse::<u32>().ok())
+        .filter(|days| (1..=MAX_POPULAR_WINDOW_DAYS).contains(days))
+        .ok_or_else(|| HttpResponse::BadRequest().json("Invalid 'window' query parameter. Use a number of days from '1d' to '365d'."))
+}
+
+/// Most viewed published posts over the last days. Counts are written in batches,
+/// so the list trails live traffic by a few seconds.
+#[utoipa::path(
+    get,
+    path = "/api/posts/popular",
+    tag = "posts",
+    params(
+        ("window" = Option<String>, Query, description = "Number of days ending today, `1d` to `365d` (default `7d`)."),
+        ("limit" = Option<u32>, Query, description = "Maximum number of posts (default 10, at most 50)."),
+    ),
+    responses(
+        (status = 200, description = "Posts with their views in the window, most viewed first.", body = Vec<PopularPost>),
+        (status = 400, description = "Invalid window.", body = String),
+    ),
+)]
+async fn get_popular_posts(
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    query: web::Query<PopularQuery>,
+) -> impl Responder {
+    let days = match parse_window_days(query.window.as_deref()) {
+        Ok(days) => days,
+        Err(resp) => return resp,
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_POPULAR_LIMIT).clamp(1, MAX_POPULAR_LIMIT);
+
+    match view_helpers::fetch_popular_posts(&db, days, limit) {
+        Ok(popular) => {
+            let (views, mut posts): (Vec<u64>, Vec<PostSummary>) =
+                popular.into_iter().map(|entry| (entry.views, entry.post)).unzip();
+            public_helpers::attach_authors(&pool, &config, &mut posts);
+            let body: Vec<PopularPost> =
+                views.into_iter().zip(posts).map(|(views, post)| PopularPost { views, post }).collect();
+            // Views change without a publish, so the list validators don't apply here.
+            HttpResponse::Ok()
+                .insert_header((header::CACHE_CONTROL, "public, max-age=60"))
+                .json(body)
+        }
+        Err(e) => {
+            log::error!("Failed to fetch popular posts: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// GET /api/posts?ids=a,b,c: multi-get of published posts, in the order requested.
+/// Ids that don't (or no longer) exist are left out of `items`.
+#[utoipa::path(
+    get,
+    path = "/api/posts",
+    tag = "posts",
+    params(
+        ("ids" = String, Query, description = "Comma-separated post ids (at most 100)."),
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "The posts that exist, in the requested order.", body = PaginatedList<PostSummary>),
+        (status = 304, description = "The client's copy is current."),
+        (status = 400, description = "Missing, malformed or too many ids.", body = String),
+    ),
+)]
+async fn get_posts_by_ids(
+    req: HttpRequest,
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    query: web::Query<PostIdsQuery>,
+) -> impl Responder {
+    let ids: Vec<&str> = match query.ids.as_deref() {
+        Some(raw) => raw.split(',').map(str::trim).filter(|id| !id.is_empty()).collect(),
+        None => Vec::new(),
+    };
+    if ids.is_empty() {
+        return HttpResponse::BadRequest().json("A non-empty 'ids' query parameter is required.");
+    }
+    if ids.len() > MAX_BATCH_IDS {
+        return HttpResponse::BadRequest().json(format!("At most {} ids can be requested at once.", MAX_BATCH_IDS));
+    }
+
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match public_helpers::fetch_posts_by_ids(&db, &ids) {
+        Ok(Some(posts)) => posts_with_validators(&req, &validators, &db, &pool, &config, posts),
+        Ok(None) => HttpResponse::BadRequest().json("Invalid post id in 'ids'."),
+        Err(e) => {
+            log::error!("Failed to fetch posts by ids: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/latest",
+    tag = "posts",
+    params(
+        ("limit" = Option<u32>, Query, description = "Page size."),
+        ("offset" = Option<u32>, Query, description = "Number of posts to skip."),
+        ("cursor" = Option<String>, Query, description = "Opaque keyset cursor from `next_cursor`; empty for the first page. Replaces `offset`."),
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "One page of posts.", body = PaginatedList<PostSummary>),
+        (status = 304, description = "The client's copy is current."),
+        (status = 400, description = "Invalid cursor or field selection.", body = String),
+    ),
+)]
+async fn get_latest_posts(
+    req: HttpRequest,
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    query: web::Query<ApiQuery>,
+) -> impl Responder {
     let limit = query.limit.unwrap_or(10);
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    if let Some(raw_cursor) = query.cursor.as_deref() {
+        let cursor = match parse_cursor(raw_cursor) {
+            Ok(c) => c,
+            Err(resp) => return resp,
+        };
+        return match public_helpers::fetch_latest_posts_after(&db, cursor.as_ref(), limit) {
+            Ok(page) => posts_with_validators(&req, &validators, &db, &pool, &config, page),
+            Err(e) => {
+                log::error!("Failed to fetch latest posts by cursor: {}", e);
+                HttpResponse::InternalServerError().finish()
+            }
+        };
+    }
+
     let offset = query.offset.unwrap_or(0);
 
     match public_helpers::fetch_latest_posts(&db, limit, offset) {
-        Ok(posts) => HttpResponse::Ok().json(posts),
+        Ok(posts) => posts_with_validators(&req, &validators, &db, &pool, &config, posts),
         Err(e) => {
             log::error!("Failed to fetch latest posts: {}", e);
             HttpResponse::InternalServerError().finish()
@@ -81,17 +958,56 @@ async fn get_latest_posts(db: web::Data<Database>, query: web::Query<ApiQuery>)
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/posts/tag/{tag}",
+    tag = "posts",
+    params(
+        ("tag" = String, Path, description = "Tag or tag path, e.g. `rust/actix`."),
+        ("limit" = Option<u32>, Query, description = "Page size."),
+        ("offset" = Option<u32>, Query, description = "Number of posts to skip."),
+        ("cursor" = Option<String>, Query, description = "Opaque keyset cursor from `next_cursor`; empty for the first page. Replaces `offset`."),
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "One page of posts.", body = PaginatedList<PostSummary>),
+        (status = 304, description = "The client's copy is current."),
+        (status = 400, description = "Invalid cursor or field selection.", body = String),
+    ),
+)]
 async fn get_posts_by_tag(
+    req: HttpRequest,
     tag: web::Path<String>,
     db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
     query: web::Query<ApiQuery>,
 ) -> impl Responder {
     let limit = query.limit.unwrap_or(20);
-    let offset = query.offset.unwrap_or(0);
     let tag_value = tag.into_inner();
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    if let Some(raw_cursor) = query.cursor.as_deref() {
+        let cursor = match parse_cursor(raw_cursor) {
+            Ok(c) => c,
+            Err(resp) => return resp,
+        };
+        return match public_helpers::fetch_posts_by_tag_after(&tag_value, &db, cursor.as_ref(), limit) {
+            Ok(page) => posts_with_validators(&req, &validators, &db, &pool, &config, page),
+            Err(e) => {
+                log::error!("Failed to fetch posts by tag '{}' by cursor: {}", tag_value, e);
+                HttpResponse::InternalServerError().finish()
+            }
+        };
+    }
+
+    let offset = query.offset.unwrap_or(0);
 
     match public_helpers::fetch_posts_by_tag(&tag_value, &db, limit, offset) {
-        Ok(posts) => HttpResponse::Ok().json(posts),
+        Ok(posts) => posts_with_validators(&req, &validators, &db, &pool, &config, posts),
         Err(e) => {
             log::error!("Failed to fetch posts by tag '{}': {}", tag_value, e);
             HttpResponse::InternalServerError().finish()
@@ -99,8 +1015,31 @@ async fn get_posts_by_tag(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/posts/search",
+    tag = "posts",
+    params(
+        ("q" = String, Query, description = "Search keyword (or full-text query with `mode=fulltext`)."),
+        ("mode" = Option<String>, Query, description = "`keyword` (default) or `fulltext`."),
+        ("fuzzy" = Option<bool>, Query, description = "Keyword mode: typo-tolerant match on tags, keywords and title words, closest first. Offset pagination only."),
+        ("limit" = Option<u32>, Query, description = "Page size."),
+        ("offset" = Option<u32>, Query, description = "Number of posts to skip."),
+        ("cursor" = Option<String>, Query, description = "Opaque keyset cursor from `next_cursor`; empty for the first page. Replaces `offset`."),
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "One page of posts.", body = PaginatedList<PostSummary>),
+        (status = 304, description = "The client's copy is current."),
+        (status = 400, description = "Missing query, invalid mode or cursor, or a fuzzy query over 100 characters or 8 words.", body = String),
+        (status = 429, description = "Rate limit exceeded; see `Retry-After`."),
+    ),
+)]
 async fn search_posts_by_keyword(
+    req: HttpRequest,
     db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
     query: web::Query<ApiQuery>,
 ) -> impl Responder {
     let keyword_query = match query.q.as_deref() {
@@ -109,10 +1048,73 @@ async fn search_posts_by_keyword(
     };
 
     let limit = query.limit.unwrap_or(10);
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match query.mode.as_deref() {
+        None | Some("keyword") => {}
+        Some("fulltext") if query.fuzzy == Some(true) => {
+            return HttpResponse::BadRequest().json("'fuzzy' is only supported in keyword mode.");
+        }
+        Some("fulltext") => {
+            if query.cursor.is_some() {
+                return HttpResponse::BadRequest().json("Full-text search is ranked by relevance and only supports 'offset' pagination.");
+            }
+            let offset = query.offset.unwrap_or(0);
+            return match public_helpers::search_posts_fulltext(keyword_query, &db, limit, offset) {
+                Ok(posts) => posts_with_validators(&req, &validators, &db, &pool, &config, posts),
+                Err(e) => {
+                    log::error!("Failed to run full-text search for '{}': {}", keyword_query, e);
+                    HttpResponse::InternalServerError().finish()
+                }
+            };
+        }
+        Some(_) => return HttpResponse::BadRequest().json("Invalid 'mode' query parameter. Use 'keyword' or 'fulltext'."),
+    }
+
+    if query.fuzzy == Some(true) {
+        if query.cursor.is_some() {
+            return HttpResponse::BadRequest().json("Fuzzy search is ranked by closeness and only supports 'offset' pagination.");
+        }
+        if keyword_query.chars().count() > fuzzy_index_operations::MAX_QUERY_CHARS
+            || keyword_query.split_whitespace().count() > fuzzy_index_operations::MAX_QUERY_WORDS
+        {
+            return HttpResponse::BadRequest().json(format!(
+                "Fuzzy search queries are limited to {} characters and {} words.",
+                fuzzy_index_operations::MAX_QUERY_CHARS,
+                fuzzy_index_operations::MAX_QUERY_WORDS
+            ));
+        }
+        let offset = query.offset.unwrap_or(0);
+        return match public_helpers::search_posts_fuzzy(keyword_query, &db, FuzzySources::default(), limit, offset) {
+            Ok(posts) => posts_with_validators(&req, &validators, &db, &pool, &config, posts),
+            Err(e) => {
+                log::error!("Failed to run fuzzy search for '{}': {}", keyword_query, e);
+                HttpResponse::InternalServerError().finish()
+            }
+        };
+    }
+
+    if let Some(raw_cursor) = query.cursor.as_deref() {
+        let cursor = match parse_cursor(raw_cursor) {
+            Ok(c) => c,
+            Err(resp) => return resp,
+        };
+        return match public_helpers::search_posts_by_keyword_after(keyword_query, &db, cursor.as_ref(), limit) {
+            Ok(page) => posts_with_validators(&req, &validators, &db, &pool, &config, page),
+            Err(e) => {
+                log::error!("Failed to search posts by keyword '{}' by cursor: {}", keyword_query, e);
+                HttpResponse::InternalServerError().finish()
+            }
+        };
+    }
+
     let offset = query.offset.unwrap_or(0);
 
     match public_helpers::search_posts_by_keyword(keyword_query, &db, limit, offset) {
-        Ok(posts) => HttpResponse::Ok().json(posts),
+        Ok(posts) => posts_with_validators(&req, &validators, &db, &pool, &config, posts),
         Err(e) => {
             log::error!("Failed to search posts by keyword '{}': {}", keyword_query, e);
             HttpResponse::InternalServerError().finish()
@@ -120,11 +1122,25 @@ async fn search_posts_by_keyword(
     }
 }
 
-async fn get_available_tags(db: web::Data<Database>) -> impl Responder {
+#[utoipa::path(
+    get,
+    path = "/api/tags/available",
+    tag = "tags",
+    responses(
+        (status = 200, description = "The tag vocabulary, sorted.", body = Vec<String>),
+        (status = 304, description = "The client's copy is current."),
+    ),
+)]
+async fn get_available_tags(req: HttpRequest, db: web::Data<Database>) -> impl Responder {
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
     match public_helpers::fetch_all_available_tags(&db) {
         Ok(mut tags) => {
             tags.sort_unstable();
-            HttpResponse::Ok().json(tags)
+            json_with_validators(&validators, tags)
         },
         Err(e) => {
             log::error!("Failed to fetch available tags: {}", e);
@@ -133,31 +1149,378 @@ async fn get_available_tags(db: web::Data<Database>) -> impl Responder {
     }
 }
 
+/// Nested tag taxonomy with published post counts per node.
+#[utoipa::path(
+    get,
+    path = "/api/tags/tree",
+    tag = "tags",
+    responses(
+        (status = 200, description = "Hierarchical tags with post counts.", body = Vec<TagTreeNode>),
+        (status = 304, description = "The client's copy is current."),
+    ),
+)]
+async fn get_tag_tree(req: HttpRequest, db: web::Data<Database>) -> impl Responder {
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match public_helpers::fetch_tag_tree(&db) {
+        Ok(tree) => json_with_validators(&validators, tree),
+        Err(e) => {
+            log::error!("Failed to build tag tree: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// GET /api/posts/archive: published post counts per month, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/posts/archive",
+    tag = "archive",
+    responses(
+        (status = 200, description = "Published posts per month, newest first.", body = Vec<ArchiveMonth>),
+        (status = 304, description = "The client's copy is current."),
+    ),
+)]
+async fn get_archive_counts(req: HttpRequest, db: web::Data<Database>) -> impl Responder {
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match public_helpers::fetch_archive_month_counts(&db) {
+        Ok(months) => json_with_validators(&validators, months),
+        Err(e) => {
+            log::error!("Failed to build archive counts: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/archive/{year}",
+    tag = "archive",
+    params(
+        ("year" = i32, Path, description = "Calendar year (UTC)."),
+        ("limit" = Option<u32>, Query, description = "Page size."),
+        ("offset" = Option<u32>, Query, description = "Number of posts to skip."),
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "One page of posts.", body = PaginatedList<PostSummary>),
+        (status = 304, description = "The client's copy is current."),
+        (status = 400, description = "Invalid year.", body = String),
+    ),
+)]
+async fn get_archive_year(
+    req: HttpRequest,
+    path: web::Path<i32>,
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    query: web::Query<ApiQuery>,
+) -> impl Responder {
+    respond_with_archive(&req, &db, &pool, &config, path.into_inner(), None, &query)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/archive/{year}/{month}",
+    tag = "archive",
+    params(
+        ("year" = i32, Path, description = "Calendar year (UTC)."),
+        ("month" = u32, Path, description = "Month, 1-12."),
+        ("limit" = Option<u32>, Query, description = "Page size."),
+        ("offset" = Option<u32>, Query, description = "Number of posts to skip."),
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "One page of posts.", body = PaginatedList<PostSummary>),
+        (status = 304, description = "The client's copy is current."),
+        (status = 400, description = "Invalid year or month.", body = String),
+    ),
+)]
+async fn get_archive_month(
+    req: HttpRequest,
+    path: web::Path<(i32, u32)>,
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    query: web::Query<ApiQuery>,
+) -> impl Responder {
+    let (year, month) = path.into_inner();
+    respond_with_archive(&req, &db, &pool, &config, year, Some(month), &query)
+}
+
+fn respond_with_archive(
+    req: &HttpRequest,
+    db: &web::Data<Database>,
+    pool: &web::Data<DbPool>,
+    config: &Config,
+    year: i32,
+    month: Option<u32>,
+    query: &ApiQuery,
+) -> HttpResponse {
+    let limit = query.limit.unwrap_or(20);
+    let offset = query.offset.unwrap_or(0);
+    let validators = match check_list_validators(req, db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match public_helpers::fetch_archive_posts(db, year, month, limit, offset) {
+        Ok(Some(posts)) => posts_with_validators(req, &validators, db, pool, config, posts),
+        Ok(None) => HttpResponse::BadRequest().json("Invalid year or month."),
+        Err(e) => {
+            log::error!("Failed to fetch archive for {}/{:?}: {}", year, month, e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// GET /api/authors: contributors with at least one published post.
+#[utoipa::path(
+    get,
+    path = "/api/authors",
+    tag = "authors",
+    params(
+        ("limit" = Option<u32>, Query, description = "Page size."),
+        ("offset" = Option<u32>, Query, description = "Number of authors to skip."),
+    ),
+    responses(
+        (status = 200, description = "Contributors with at least one published post.", body = PaginatedList<AuthorProfile>),
+        (status = 304, description = "The client's copy is current."),
+    ),
+)]
+async fn get_authors(
+    req: HttpRequest,
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    query: web::Query<ApiQuery>,
+) -> impl Responder {
+    let limit = query.limit.unwrap_or(20);
+    let offset = query.offset.unwrap_or(0);
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match public_helpers::fetch_authors(&pool, &config, limit, offset) {
+        Ok(authors) => json_with_validators(&validators, authors),
+        Err(e) => {
+            log::error!("Failed to fetch authors: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/authors/{username}",
+    tag = "authors",
+    params(("username" = String, Path, description = "Contributor username.")),
+    responses(
+        (status = 200, description = "The author's public profile.", body = AuthorProfile),
+        (status = 304, description = "The client's copy is current."),
+        (status = 404, description = "Unknown author."),
+    ),
+)]
+async fn get_author(
+    req: HttpRequest,
+    username: web::Path<String>,
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+) -> impl Responder {
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match public_helpers::fetch_author(&pool, &config, &username) {
+        Ok(Some(author)) => json_with_validators(&validators, author),
+        Ok(None) => HttpResponse::NotFound().body("Author not found"),
+        Err(e) => {
+            log::error!("Failed to fetch author '{}': {}", username, e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/authors/{username}/posts",
+    tag = "authors",
+    params(
+        ("username" = String, Path, description = "Contributor username."),
+        ("limit" = Option<u32>, Query, description = "Page size."),
+        ("offset" = Option<u32>, Query, description = "Number of posts to skip."),
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "One page of posts.", body = PaginatedList<PostSummary>),
+        (status = 304, description = "The client's copy is current."),
+        (status = 404, description = "Unknown author."),
+    ),
+)]
+async fn get_author_posts(
+    req: HttpRequest,
+    username: web::Path<String>,
+    db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    query: web::Query<ApiQuery>,
+) -> impl Responder {
+    let limit = query.limit.unwrap_or(20);
+    let offset = query.offset.unwrap_or(0);
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match public_helpers::fetch_author_posts(&db, &pool, &config, &username, limit, offset) {
+        Ok(Some(posts)) => shaped_posts_with_validators(&req, &validators, &db, &posts),
+        Ok(None) => HttpResponse::NotFound().body("Author not found"),
+        Err(e) => {
+            log::error!("Failed to fetch posts for author '{}': {}", username, e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// Builds the tag expression for /api/posts/filter from its query parameters.
+fn build_tag_filter(query: &TagFilterQuery) -> Result<TagExpr, String> {
+    let all: Vec<String> = query.tags.iter().chain(&query.all).cloned().collect();
+    // Checked before building, so huge lists never become a deep expression tree.
+    if all.len() + query.any.len() + query.exclude.len() > tag_filter_operations::MAX_TAG_TERMS {
+        return Err(format!("Filters use more than {} tags.", tag_filter_operations::MAX_TAG_TERMS));
+    }
+    let from_sets = TagExpr::from_sets(&all, &query.any, &query.exclude);
+    let from_expr = match query.expr.as_deref().map(str::trim).filter(|e| !e.is_empty()) {
+        Some(e) => Some(tag_filter_operations::parse_tag_expression(e)?),
+        None => None,
+    };
+
+    let expr = match (from_sets, from_expr) {
+        (Some(a), Some(b)) => TagExpr::And(Box::new(a), Box::new(b)),
+        (Some(e), None) | (None, Some(e)) => e,
+        (None, None) => {
+            return Err("At least one of 'all', 'any', 'exclude', 'tags' or 'expr' must be provided.".to_string())
+        }
+    };
+    expr.check_term_limit()?;
+    Ok(expr)
+}
+
 /// Handles requests to the GET /api/posts/filter endpoint.
+#[utoipa::path(
+    get,
+    path = "/api/posts/filter",
+    tag = "posts",
+    params(
+        ("all" = Option<String>, Query, description = "Comma-separated tags that must all be present (alias: `tags`)."),
+        ("any" = Option<String>, Query, description = "Comma-separated tags of which at least one must be present."),
+        ("exclude" = Option<String>, Query, description = "Comma-separated tags that must be absent."),
+        ("expr" = Option<String>, Query, description = "Boolean expression, e.g. `rust AND (actix OR axum) NOT draft`."),
+        ("limit" = Option<u32>, Query, description = "Page size."),
+        ("offset" = Option<u32>, Query, description = "Number of posts to skip."),
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "One page of posts.", body = PaginatedList<PostSummary>),
+        (status = 304, description = "The client's copy is current."),
+        (status = 400, description = "No filter given, invalid expression or more than 32 tags in total.", body = String),
+    ),
+)]
 async fn filter_posts_by_tags(
+    req: HttpRequest,
     db: web::Data<Database>,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
     query: web::Query<TagFilterQuery>,
 ) -> impl Responder {
-    if query.tags.is_empty() {
-        return HttpResponse::BadRequest()
-            .body("Error: At least one 'tag' query parameter must be provided.");
-    }
+    let expr = match build_tag_filter(&query) {
+        Ok(e) => e,
+        Err(message) => return HttpResponse::BadRequest().json(message),
+    };
 
     // --- PAGINATION IS HANDLED HERE ---
     // If 'limit' or 'offset' are not in the URL, use the specified defaults.
     let limit = query.limit.unwrap_or(20);
     let offset = query.offset.unwrap_or(0);
 
-    // Call the helper function with the validated and prepared parameters.
-    match public_helpers::fetch_posts_by_tags_intersection(&db, &query.tags, limit, offset) {
-        Ok(posts) => HttpResponse::Ok().json(posts),
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match public_helpers::fetch_posts_by_tag_expression(&db, &expr, limit, offset) {
+        Ok(posts) => posts_with_validators(&req, &validators, &db, &pool, &config, posts),
         Err(e) => {
-            log::error!(
-                "Failed to fetch posts by tags intersection '{:?}': {}",
-                &query.tags,
-                e
-            );
+            log::error!("Failed to filter posts by tag expression {:?}: {}", expr, e);
             HttpResponse::InternalServerError().finish()
         }
     }
-}
\ No newline at end of file
+}
+
+/// Search-as-you-type completions: tags, search keywords and post titles starting with `q`.
+#[utoipa::path(
+    get,
+    path = "/api/suggest",
+    tag = "tags",
+    params(
+        ("q" = String, Query, description = "The typed prefix (case-insensitive)."),
+        ("limit" = Option<u32>, Query, description = "Completions per group; default 5, at most 20."),
+        ("types" = Option<String>, Query, description = "Comma-separated subset of `tags`, `keywords`, `titles`, `vocabulary` (admin-managed tags only); all but `vocabulary` by default."),
+    ),
+    responses(
+        (status = 200, description = "Tags and keywords by post count, titles newest first.", body = Suggestions),
+        (status = 304, description = "The client's copy is current."),
+        (status = 400, description = "Missing or too long prefix, invalid limit or type.", body = String),
+    ),
+)]
+async fn get_suggestions(req: HttpRequest, db: web::Data<Database>, query: web::Query<SuggestQuery>) -> impl Responder {
+    let prefix = match query.q.as_deref().map(str::trim) {
+        Some(q) if !q.is_empty() && q.chars().count() <= MAX_SUGGEST_PREFIX_CHARS => q,
+        _ => return HttpResponse::BadRequest().json(format!("A 'q' query parameter of 1 to {} characters is required.", MAX_SUGGEST_PREFIX_CHARS)),
+    };
+
+    let limit = query.limit.unwrap_or(DEFAULT_SUGGEST_LIMIT);
+    if limit == 0 || limit > MAX_SUGGEST_LIMIT {
+        return HttpResponse::BadRequest().json(format!("'limit' must be between 1 and {}.", MAX_SUGGEST_LIMIT));
+    }
+
+    let kinds = match query.types.as_deref() {
+        None => SuggestKinds::default(),
+        Some(raw) => {
+            let mut kinds = SuggestKinds { tags: false, keywords: false, titles: false, vocabulary: false };
+            for kind in raw.split(',').map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()) {
+                match kind.as_str() {
+                    "tags" => kinds.tags = true,
+                    "keywords" => kinds.keywords = true,
+                    "titles" => kinds.titles = true,
+                    "vocabulary" => kinds.vocabulary = true,
+                    _ => return HttpResponse::BadRequest().json(format!("Unknown value '{}' in 'types'. Supported: tags, keywords, titles, vocabulary.", kind)),
+                }
+            }
+            kinds
+        }
+    };
+
+    let validators = match check_list_validators(&req, &db) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match public_helpers::fetch_suggestions(prefix, &db, kinds, limit) {
+        Ok(suggestions) => json_with_validators(&validators, suggestions),
+        Err(e) => {
+            log::error!("Failed to fetch suggestions for '{}': {}", prefix, e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
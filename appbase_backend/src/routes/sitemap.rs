@@ -0,0 +1,34 @@
+use crate::config::Config;
+use crate::helper::sitemap_helpers;
+use actix_web::{web, HttpResponse, Responder};
+use redb::Database;
+
+pub fn config_sitemap(cfg: &mut web::ServiceConfig) {
+    cfg.route("/sitemap.xml", web::get().to(get_root_sitemap))
+        .route("/sitemaps/posts-{page}.xml", web::get().to(get_sitemap_page));
+}
+
+async fn get_root_sitemap(db: web::Data<Database>, config: web::Data<Config>) -> impl Responder {
+    match sitemap_helpers::build_root_sitemap(&db, &config.site) {
+        Ok(xml) => HttpResponse::Ok().content_type("application/xml; charset=utf-8").body(xml),
+        Err(e) => {
+            log::error!("Failed to build sitemap: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+async fn get_sitemap_page(
+    page: web::Path<u32>,
+    db: web::Data<Database>,
+    config: web::Data<Config>,
+) -> impl Responder {
+    match sitemap_helpers::build_sitemap_page(&db, &config.site, page.into_inner()) {
+        Ok(Some(xml)) => HttpResponse::Ok().content_type("application/xml; charset=utf-8").body(xml),
+        Ok(None) => HttpResponse::NotFound().body("Sitemap not found"),
+        Err(e) => {
+            log::error!("Failed to build sitemap page: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
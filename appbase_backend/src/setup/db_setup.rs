@@ -2,6 +2,9 @@ use redb::{Database, TableDefinition, CommitError, StorageError, TableError, Tra
 use rusqlite::{Connection, Result as RusqliteResult, Transaction};
 use thiserror::Error;
 
+use crate::helper::reaction_helpers;
+use crate::models::db_operations::{comment_operations, fulltext_index_operations, fuzzy_index_operations, posts_db_operations, reaction_operations, suggest_operations, view_operations};
+
 #[derive(Error, Debug)]
 pub enum SetupError {
     #[error("Rusqlite error: {0}")]
@@ -78,12 +81,60 @@ pub fn setup_contributors_db(conn: &mut Connection) -> Result<(), SetupError> {
         [],
     )?;
 
+    println!("- Creating 'contributor_profiles' table...");
+    tx.execute(CREATE_CONTRIBUTOR_PROFILES, [])?;
+
+    println!("- Creating 'webhooks' and 'webhook_deliveries' tables...");
+    tx.execute_batch(CREATE_WEBHOOK_TABLES)?;
+
     seed_initial_settings(&tx)?;
 
     tx.commit()?;
     Ok(())
 }
 
+const CREATE_CONTRIBUTOR_PROFILES: &str = "CREATE TABLE IF NOT EXISTS contributor_profiles (
+    user_id INTEGER PRIMARY KEY,
+    display_name TEXT,
+    bio TEXT,
+    avatar_media_id TEXT,
+    links TEXT, -- JSON array of {label, url}
+    updated_at TEXT,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+)";
+
+// Timestamps are RFC 3339 UTC with whole seconds, so they sort as text.
+const CREATE_WEBHOOK_TABLES: &str = "CREATE TABLE IF NOT EXISTS webhooks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    events TEXT NOT NULL, -- comma-separated event names
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    webhook_id INTEGER NOT NULL,
+    event TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    status TEXT NOT NULL CHECK(status IN ('pending', 'delivered', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TEXT,
+    last_attempt_at TEXT,
+    response_status INTEGER,
+    last_error TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (webhook_id) REFERENCES webhooks(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries (status, next_attempt_at);";
+
+/// Creates any contributors.db tables that were added after a database was first set up.
+/// Called on server start, like `migrate_posts_db`.
+pub fn migrate_contributors_db(conn: &Connection) -> Result<(), SetupError> {
+    conn.execute(CREATE_CONTRIBUTOR_PROFILES, [])?;
+    conn.execute_batch(CREATE_WEBHOOK_TABLES)?;
+    Ok(())
+}
+
 fn seed_initial_settings(tx: &Transaction) -> RusqliteResult<()> {
     println!("- Seeding initial settings...");
     let default_prefix = "contributors";
@@ -108,6 +159,12 @@ fn seed_initial_settings(tx: &Transaction) -> RusqliteResult<()> {
     )?;
     println!("  > Default allowed MIME types set to: (empty - admin must configure)");
 
+    tx.execute(
+        "INSERT OR IGNORE INTO settings (key, value) VALUES ('reaction_kinds', ?1)",
+        [reaction_helpers::DEFAULT_REACTION_KINDS],
+    )?;
+    println!("  > Default reaction kinds set to: {}", reaction_helpers::DEFAULT_REACTION_KINDS);
+
     Ok(())
 }
 
@@ -155,6 +212,79 @@ pub fn setup_posts_db(db: &Database) -> Result<(), SetupError> {
         write_txn.open_table(PENDING_METADATA)?;
         // --- END NEW ---
 
+        println!("- Creating 'fulltext_postings' table in Redb...");
+        write_txn.open_table(fulltext_index_operations::FULLTEXT_POSTINGS)?;
+
+        println!("- Creating 'fulltext_docs' table in Redb...");
+        write_txn.open_table(fulltext_index_operations::FULLTEXT_DOCS)?;
+
+        println!("- Creating 'fulltext_stats' table in Redb...");
+        write_txn.open_table(fulltext_index_operations::FULLTEXT_STATS)?;
+
+        println!("- Creating 'publish_state' table in Redb...");
+        write_txn.open_table(posts_db_operations::PUBLISH_STATE)?;
+
+        println!("- Creating 'slug_index' table in Redb...");
+        write_txn.open_table(posts_db_operations::SLUG_INDEX)?;
+
+        println!("- Creating 'post_slugs' table in Redb...");
+        write_txn.open_table(posts_db_operations::POST_SLUGS)?;
+
+        println!("- Creating 'title_word_index' table in Redb...");
+        write_txn.open_table(suggest_operations::TITLE_WORD_INDEX)?;
+
+        println!("- Creating 'fuzzy_terms' table in Redb...");
+        write_txn.open_table(fuzzy_index_operations::FUZZY_TERMS)?;
+
+        println!("- Creating 'fuzzy_trigrams' table in Redb...");
+        write_txn.open_table(fuzzy_index_operations::FUZZY_TRIGRAMS)?;
+
+        println!("- Creating 'post_daily_views' table in Redb...");
+        write_txn.open_table(view_operations::POST_DAILY_VIEWS)?;
+
+        println!("- Creating 'daily_post_views' table in Redb...");
+        write_txn.open_table(view_operations::DAILY_POST_VIEWS)?;
+
+        println!("- Creating 'pending_comments' table in Redb...");
+        write_txn.open_table(comment_operations::PENDING_COMMENTS)?;
+
+        println!("- Creating 'comments' table in Redb...");
+        write_txn.open_table(comment_operations::COMMENTS)?;
+
+        println!("- Creating 'comment_posts' table in Redb...");
+        write_txn.open_table(comment_operations::COMMENT_POSTS)?;
+
+        println!("- Creating 'reaction_counts' table in Redb...");
+        write_txn.open_table(reaction_operations::REACTION_COUNTS)?;
+
+        println!("- Creating 'reaction_votes' table in Redb...");
+        write_txn.open_table(reaction_operations::REACTION_VOTES)?;
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+/// Creates any posts.db tables that were added after a database was first set up.
+/// Called on server start, so existing installations pick up new tables without a manual step.
+pub fn migrate_posts_db(db: &Database) -> Result<(), SetupError> {
+    let write_txn = db.begin_write()?;
+    {
+        write_txn.open_table(fulltext_index_operations::FULLTEXT_POSTINGS)?;
+        write_txn.open_table(fulltext_index_operations::FULLTEXT_DOCS)?;
+        write_txn.open_table(fulltext_index_operations::FULLTEXT_STATS)?;
+        write_txn.open_table(posts_db_operations::PUBLISH_STATE)?;
+        write_txn.open_table(posts_db_operations::SLUG_INDEX)?;
+        write_txn.open_table(posts_db_operations::POST_SLUGS)?;
+        write_txn.open_table(suggest_operations::TITLE_WORD_INDEX)?;
+        write_txn.open_table(fuzzy_index_operations::FUZZY_TERMS)?;
+        write_txn.open_table(fuzzy_index_operations::FUZZY_TRIGRAMS)?;
+        write_txn.open_table(view_operations::POST_DAILY_VIEWS)?;
+        write_txn.open_table(view_operations::DAILY_POST_VIEWS)?;
+        write_txn.open_table(comment_operations::PENDING_COMMENTS)?;
+        write_txn.open_table(comment_operations::COMMENTS)?;
+        write_txn.open_table(comment_operations::COMMENT_POSTS)?;
+        write_txn.open_table(reaction_operations::REACTION_COUNTS)?;
+        write_txn.open_table(reaction_operations::REACTION_VOTES)?;
     }
     write_txn.commit()?;
     Ok(())
@@ -1,6 +1,7 @@
 use clap::{Parser, Subcommand};
 use appbase_backend::config::Config;
 use appbase_backend::setup::db_setup;
+use appbase_backend::models::db_operations::{fulltext_index_operations, fuzzy_index_operations, outline_operations, posts_db_operations, suggest_operations};
 use rusqlite::{params, Connection};
 use bcrypt::{hash, DEFAULT_COST};
 use redb::Database;
@@ -36,7 +37,13 @@ enum Commands {
 enum DbAction {
     Setup {
         db_type: Option<String>,
-    }
+    },
+    /// Rebuilds the full-text, title suggestion and fuzzy search indices from all published posts.
+    ReindexSearch,
+    /// Assigns slugs to published posts that were approved before slugs existed.
+    BackfillSlugs,
+    /// Computes word count, reading time and outline for posts published before they were stored.
+    BackfillOutlines,
 }
 
 #[derive(Subcommand, Debug)]
@@ -83,6 +90,9 @@ fn main() {
                     }
                 }
             }
+            DbAction::ReindexSearch => reindex_search(&config),
+            DbAction::BackfillSlugs => backfill_slugs(&config),
+            DbAction::BackfillOutlines => backfill_outlines(&config),
         },
         Commands::Admin { action } => match action {
             AdminAction::Create { username, password } => {
@@ -140,6 +150,62 @@ fn setup_posts_database(config: &Config) {
     }
 }
 
+fn reindex_search(config: &Config) {
+    let db_path = config.posts_db_path();
+    if !db_path.exists() {
+        eprintln!("❌ Error: Posts database not found at '{}'. Please run `setup_cli db setup` first.", db_path.display());
+        return;
+    }
+    let db = Database::open(&db_path).expect("Could not open posts database.");
+    if let Err(e) = db_setup::migrate_posts_db(&db) {
+        eprintln!("❌ Error creating search index tables: {}", e);
+        return;
+    }
+    match fulltext_index_operations::rebuild_index(&db) {
+        Ok(count) => println!("✅ Full-text search index rebuilt for {} published post(s).", count),
+        Err(e) => eprintln!("❌ Error rebuilding search index: {}", e),
+    }
+    match suggest_operations::rebuild_title_index(&db) {
+        Ok(count) => println!("✅ Title suggestion index rebuilt for {} published post(s).", count),
+        Err(e) => eprintln!("❌ Error rebuilding title suggestion index: {}", e),
+    }
+    // Built from the tag, keyword and title indices, so it must run last.
+    match fuzzy_index_operations::rebuild_index(&db) {
+        Ok(count) => println!("✅ Fuzzy search index rebuilt with {} term(s).", count),
+        Err(e) => eprintln!("❌ Error rebuilding fuzzy search index: {}", e),
+    }
+}
+
+fn backfill_slugs(config: &Config) {
+    let db_path = config.posts_db_path();
+    if !db_path.exists() {
+        eprintln!("❌ Error: Posts database not found at '{}'. Please run `setup_cli db setup` first.", db_path.display());
+        return;
+    }
+    let db = Database::open(&db_path).expect("Could not open posts database.");
+    if let Err(e) = db_setup::migrate_posts_db(&db) {
+        eprintln!("❌ Error creating slug tables: {}", e);
+        return;
+    }
+    match posts_db_operations::backfill_slugs(&db) {
+        Ok(count) => println!("✅ Assigned slugs to {} published post(s).", count),
+        Err(e) => eprintln!("❌ Error assigning slugs: {}", e),
+    }
+}
+
+fn backfill_outlines(config: &Config) {
+    let db_path = config.posts_db_path();
+    if !db_path.exists() {
+        eprintln!("❌ Error: Posts database not found at '{}'. Please run `setup_cli db setup` first.", db_path.display());
+        return;
+    }
+    let db = Database::open(&db_path).expect("Could not open posts database.");
+    match outline_operations::backfill_outlines(&db) {
+        Ok(count) => println!("✅ Computed reading time and outline for {} published post(s).", count),
+        Err(e) => eprintln!("❌ Error computing post outlines: {}", e),
+    }
+}
+
 fn create_admin_user(config: &Config, username: &str, password: &str) {
     let db_path = config.users_db_path();
     if !db_path.exists() {